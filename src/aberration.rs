@@ -0,0 +1,149 @@
+//! Annual aberration — Meeus, *Astronomical Algorithms*, 2nd ed., Chapter 23.
+//!
+//! Two forms are provided: the classical first order formulae of Meeus
+//! (eqs. 23.2 and 23.3) built on the longitude of the Sun, and the rigorous
+//! relativistic vector form using the velocity of the Earth, accurate as long
+//! as the velocity is.
+
+use crate::ecliptic::EclipticPosition;
+use crate::julian_day::JulianDay;
+use crate::nutation::get_mean_obliquity;
+use crate::sun::{get_earth_velocity, get_sun_position};
+use crate::EqPosition;
+
+/// Constant of aberration κ in arcseconds.
+pub const ABERRATION_CONSTANT: f64 = 20.495_52;
+
+/// Speed of light in AU/day.
+pub const SPEED_OF_LIGHT: f64 = 173.144_632_684_7;
+
+/// Longitude of the Sun ☉, eccentricity e and longitude of the perihelion π
+/// of the Earth's orbit (Meeus p. 151), angles in radians.
+fn solar_terms(jd: &JulianDay) -> (f64, f64, f64) {
+    let t = (jd.get_value() - 2_451_545.0) / 36_525.0;
+    let e = 0.016_708_634 - 0.000_042_037 * t - 0.000_000_126_7 * t * t;
+    let pi = 102.937_35 + 1.719_46 * t + 0.000_46 * t * t;
+
+    (
+        get_sun_position(jd).true_longitude.to_radians(),
+        e,
+        pi.to_radians(),
+    )
+}
+
+/// Corrects ecliptic coordinates for annual aberration (Meeus eq. 23.2).
+#[must_use]
+pub fn get_ecl_aber(ecl: &EclipticPosition, jd: &JulianDay) -> EclipticPosition {
+    let (sun, e, pi) = solar_terms(jd);
+    let kappa = ABERRATION_CONSTANT / 3_600.0;
+    let lambda = ecl.longitude.to_radians();
+    let beta = ecl.latitude.to_radians();
+
+    let d_lambda = (-kappa * (sun - lambda).cos() + e * kappa * (pi - lambda).cos()) / beta.cos();
+    let d_beta = -kappa * beta.sin() * ((sun - lambda).sin() - e * (pi - lambda).sin());
+
+    EclipticPosition::new(ecl.longitude + d_lambda, ecl.latitude + d_beta)
+}
+
+/// Corrects equatorial coordinates referred to the equinox of date for annual
+/// aberration (Meeus eq. 23.3).
+#[must_use]
+pub fn get_equ_aber(pos: &EqPosition, jd: &JulianDay) -> EqPosition {
+    let (sun, e, pi) = solar_terms(jd);
+    let kappa = ABERRATION_CONSTANT / 3_600.0;
+    let epsilon = get_mean_obliquity(jd).to_radians();
+    let alpha = pos.ra_radians();
+    let delta = pos.dec_radians();
+
+    let d_alpha = (-kappa * (alpha.cos() * sun.cos() * epsilon.cos() + alpha.sin() * sun.sin())
+        + e * kappa * (alpha.cos() * pi.cos() * epsilon.cos() + alpha.sin() * pi.sin()))
+        / delta.cos();
+
+    let common = epsilon.tan() * delta.cos() - alpha.sin() * delta.sin();
+    let d_delta = -kappa
+        * (sun.cos() * epsilon.cos() * common + alpha.cos() * delta.sin() * sun.sin())
+        + e * kappa * (pi.cos() * epsilon.cos() * common + alpha.cos() * delta.sin() * pi.sin());
+
+    EqPosition::from_degrees(pos.ra_degrees() + d_alpha, pos.dec_degrees() + d_delta)
+        .with_epoch(pos.epoch())
+}
+
+/// Applies the relativistic aberration for an observer moving with `velocity`
+/// (AU/day, in the same frame as `pos`) to a position.
+#[must_use]
+pub fn aberrate(pos: &EqPosition, velocity: [f64; 3]) -> EqPosition {
+    let p = pos.to_cartesian();
+    let v = velocity.map(|x| x / SPEED_OF_LIGHT);
+
+    let v2 = v[0] * v[0] + v[1] * v[1] + v[2] * v[2];
+    let inv_gamma = (1.0 - v2).sqrt();
+    let p_dot_v = p[0] * v[0] + p[1] * v[1] + p[2] * v[2];
+    let w = 1.0 + p_dot_v / (1.0 + inv_gamma);
+
+    let res = [0, 1, 2].map(|i| (inv_gamma * p[i] + w * v[i]) / (1.0 + p_dot_v));
+
+    EqPosition::from_cartesian(res).with_epoch(pos.epoch())
+}
+
+/// Corrects equatorial coordinates referred to the equinox of date for annual
+/// aberration with the relativistic vector form, using the velocity of the
+/// Earth from [`get_earth_velocity`].
+#[must_use]
+pub fn get_equ_aber_relativistic(pos: &EqPosition, jd: &JulianDay) -> EqPosition {
+    aberrate(pos, get_earth_velocity(jd))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Meeus, Astronomical Algorithms, 2nd ed., Example 23.a, p. 156
+    // θ Persei at 2028 Nov 13.19 TD, mean place of date α = 41.5472°, δ = 49.3485°
+    // Expected: Δα = +30.045", Δδ = +6.697"
+    const JD: f64 = 2_462_088.69;
+    const ALPHA: f64 = 41.547_2;
+    const DELTA: f64 = 49.348_5;
+
+    #[test]
+    fn test_equ_aberration_meeus_example_23a() {
+        let pos = EqPosition::from_degrees(ALPHA, DELTA);
+        let res = get_equ_aber(&pos, &JulianDay::new(JD));
+
+        let d_alpha = (res.ra_degrees() - ALPHA) * 3_600.0;
+        let d_delta = (res.dec_degrees() - DELTA) * 3_600.0;
+        assert!((d_alpha - 30.045).abs() < 0.005, "Δα = {d_alpha}");
+        assert!((d_delta - 6.697).abs() < 0.005, "Δδ = {d_delta}");
+    }
+
+    #[test]
+    fn test_relativistic_aberration_agrees_with_meeus() {
+        let pos = EqPosition::from_degrees(ALPHA, DELTA);
+        let res = get_equ_aber_relativistic(&pos, &JulianDay::new(JD));
+
+        let d_alpha = (res.ra_degrees() - ALPHA) * 3_600.0;
+        let d_delta = (res.dec_degrees() - DELTA) * 3_600.0;
+        assert!((d_alpha - 30.045).abs() < 0.01, "Δα = {d_alpha}");
+        assert!((d_delta - 6.697).abs() < 0.01, "Δδ = {d_delta}");
+    }
+
+    #[test]
+    fn test_ecl_aberration_amplitude() {
+        let jd = JulianDay::new(JD);
+        let sun = get_sun_position(&jd).true_longitude;
+
+        // A star on the ecliptic at opposition is displaced by ~κ in longitude,
+        // one towards the apex of the Earth's motion (☉ − 90°) is not displaced.
+        let opposition = EclipticPosition::new(sun + 180.0, 0.0);
+        let res = get_ecl_aber(&opposition, &jd);
+        let d_lambda = (res.longitude - opposition.longitude) * 3_600.0;
+        assert!(
+            (d_lambda - ABERRATION_CONSTANT).abs() < 0.4,
+            "Δλ = {d_lambda}"
+        );
+        assert!(res.latitude.abs() < 1e-12);
+
+        let apex = EclipticPosition::new(sun - 90.0, 0.0);
+        let res = get_ecl_aber(&apex, &jd);
+        assert!((res.longitude - apex.longitude).abs() * 3_600.0 < 0.4);
+    }
+}
@@ -0,0 +1,185 @@
+//! Apparent place of a star — Meeus, *Astronomical Algorithms*, 2nd ed., Chapter 23.
+//!
+//! [`get_apparent_position`] reduces a catalogue position to the apparent
+//! position for a given date (libnova's `ln_get_apparent_posn`) in the order
+//! proper motion and parallax, precession, annual aberration and nutation.
+//! Aberration uses the relativistic vector form and is applied in the mean
+//! equatorial frame of date, where the velocity of the Earth is known.
+
+use crate::aberration::aberrate;
+use crate::julian_day::JulianDay;
+use crate::nutation::get_equ_nut;
+use crate::precession::{get_precession_matrix, precess_with_matrix};
+use crate::sun::{get_earth_velocity, get_sun_rect_coords};
+use crate::{Epoch, EqPosition};
+
+/// Days in a Julian year.
+const JULIAN_YEAR: f64 = 365.25;
+
+/// One AU per Julian year in km/s.
+const AU_PER_YEAR_KM_S: f64 = 4.740_470_463_5;
+
+/// Catalogue entry of a star.
+///
+/// The position is referred to the equinox it is tagged with, which is also
+/// taken as the epoch of the catalogue position for the proper motion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CatalogueEntry {
+    /// Mean position at the catalogue epoch, usually [`Epoch::J2000`].
+    pub position: EqPosition,
+    /// Proper motion in right ascension μα·cos δ in arcseconds per Julian year.
+    pub pm_ra: f64,
+    /// Proper motion in declination μδ in arcseconds per Julian year.
+    pub pm_dec: f64,
+    /// Annual parallax in arcseconds.
+    pub parallax: f64,
+    /// Radial velocity in km/s, positive when receding.
+    pub radial_velocity: f64,
+}
+
+impl CatalogueEntry {
+    /// Creates an entry with no proper motion, parallax or radial velocity.
+    #[must_use]
+    pub fn new(position: EqPosition) -> Self {
+        Self {
+            position,
+            pm_ra: 0.0,
+            pm_dec: 0.0,
+            parallax: 0.0,
+            radial_velocity: 0.0,
+        }
+    }
+
+    /// Sets the proper motion μα·cos δ and μδ in arcseconds per Julian year.
+    #[must_use]
+    pub fn with_proper_motion(mut self, pm_ra: f64, pm_dec: f64) -> Self {
+        self.pm_ra = pm_ra;
+        self.pm_dec = pm_dec;
+        self
+    }
+
+    /// Sets the annual parallax in arcseconds.
+    #[must_use]
+    pub fn with_parallax(mut self, parallax: f64) -> Self {
+        self.parallax = parallax;
+        self
+    }
+
+    /// Sets the radial velocity in km/s.
+    #[must_use]
+    pub fn with_radial_velocity(mut self, radial_velocity: f64) -> Self {
+        self.radial_velocity = radial_velocity;
+        self
+    }
+}
+
+/// Applies the space motion of the star from its catalogue epoch to `jd` and
+/// the annual parallax, returning the geocentric direction still referred to
+/// the catalogue equinox.
+///
+/// Rigorous vector form, equivalent to SOFA's `iauPmpx`; the heliocentric
+/// position of the Earth comes from [`get_sun_rect_coords`].
+#[must_use]
+pub fn get_equ_pm(entry: &CatalogueEntry, jd: &JulianDay) -> EqPosition {
+    let epoch = entry.position.epoch();
+    let years = (jd.get_value() - epoch.to_julian_day().get_value()) / JULIAN_YEAR;
+
+    let (sin_ra, cos_ra) = entry.position.ra_radians().sin_cos();
+    let (sin_dec, cos_dec) = entry.position.dec_radians().sin_cos();
+    let p = entry.position.to_cartesian();
+
+    let pm_ra = (entry.pm_ra / 3_600.0).to_radians();
+    let pm_dec = (entry.pm_dec / 3_600.0).to_radians();
+    let parallax = (entry.parallax / 3_600.0).to_radians();
+    // Radial velocity as a fractional change of the distance per year
+    let w = entry.radial_velocity / AU_PER_YEAR_KM_S * parallax;
+
+    let motion = [
+        -pm_ra * sin_ra - pm_dec * sin_dec * cos_ra + w * p[0],
+        pm_ra * cos_ra - pm_dec * sin_dec * sin_ra + w * p[1],
+        pm_dec * cos_dec + w * p[2],
+    ];
+    let earth = get_sun_rect_coords(jd).map(|x| -x);
+
+    let res = [0, 1, 2].map(|i| p[i] + years * motion[i] - parallax * earth[i]);
+    EqPosition::from_cartesian(res).with_epoch(epoch)
+}
+
+/// Computes the apparent position of a star for the given Julian Ephemeris Day,
+/// referred to the true equator and equinox of date.
+#[must_use]
+pub fn get_apparent_position(entry: &CatalogueEntry, jd: &JulianDay) -> EqPosition {
+    let astrometric = get_equ_pm(entry, jd);
+
+    let matrix = get_precession_matrix(&astrometric.epoch().to_julian_day(), jd);
+    let mean = precess_with_matrix(&astrometric, &matrix, Epoch::OfDate(*jd));
+
+    get_equ_nut(&aberrate(&mean, get_earth_velocity(jd)), jd)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Declination, RightAscension};
+
+    // Meeus, Astronomical Algorithms, 2nd ed., Examples 21.b and 23.a, pp. 135 and 156
+    // θ Persei at 2028 Nov 13.19 TD (JDE 2462088.69)
+    // J2000: α0 = 2h44m11.986s, δ0 = +49°13'42.48",
+    //        μα = +0.03425 s/yr, μδ = −0.0895"/yr
+    // Expected apparent place: α = 2h46m14.390s, δ = +49°21'07.45"
+    fn theta_persei() -> CatalogueEntry {
+        let position = EqPosition::new(
+            RightAscension::new(2, 44, 11.986),
+            Declination::new(49, 13, 42.48),
+        );
+        let pm_ra = 0.034_25 * 15.0 * position.dec_radians().cos();
+        CatalogueEntry::new(position).with_proper_motion(pm_ra, -0.089_5)
+    }
+
+    #[test]
+    fn test_apparent_position_meeus_example_23a() {
+        let jd = JulianDay::new(2_462_088.69);
+        let res = get_apparent_position(&theta_persei(), &jd);
+        let expected = EqPosition::new(
+            RightAscension::new(2, 46, 14.39),
+            Declination::new(49, 21, 7.45),
+        );
+
+        let d_alpha = (res.ra_degrees() - expected.ra_degrees()) * 3_600.0;
+        let d_delta = (res.dec_degrees() - expected.dec_degrees()) * 3_600.0;
+        assert!(d_alpha.abs() < 0.05, "Δα = {d_alpha}");
+        assert!(d_delta.abs() < 0.05, "Δδ = {d_delta}");
+        assert_eq!(res.epoch(), Epoch::OfDate(jd));
+    }
+
+    #[test]
+    fn test_proper_motion_meeus_example_21b() {
+        // Expected: α0 = 41.054063°, δ0 = 49.227750° at the date, still J2000
+        let jd = JulianDay::new(2_462_088.69);
+        let res = get_equ_pm(&theta_persei(), &jd);
+
+        assert!((res.ra_degrees() - 41.054_063).abs() < 1e-5, "{res}");
+        assert!((res.dec_degrees() - 49.227_750).abs() < 1e-5, "{res}");
+        assert_eq!(res.epoch(), Epoch::J2000);
+    }
+
+    #[test]
+    fn test_parallax_displacement() {
+        // A star at the north ecliptic pole describes a circle of radius π.
+        let pole = EqPosition::from_degrees(270.0, 66.560_7);
+        let entry = CatalogueEntry::new(pole).with_parallax(0.5);
+
+        for day in [0.0, 91.3, 182.6, 273.9] {
+            let jd = JulianDay::new(2_451_545.0 + day);
+            let res = get_equ_pm(&entry, &jd).to_cartesian();
+            let p = pole.to_cartesian();
+            let shift = (0..3)
+                .map(|i| (res[i] - p[i]).powi(2))
+                .sum::<f64>()
+                .sqrt()
+                .to_degrees()
+                * 3_600.0;
+            assert!((shift - 0.5).abs() < 0.01, "shift: {shift}");
+        }
+    }
+}
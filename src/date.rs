@@ -1,29 +1,163 @@
-use std::fmt::{Display, Formatter, Result};
+use std::fmt::{self, Display, Formatter, Write};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+use std::time::SystemTime;
 
-use crate::julian_day::{get_julian_day, JulianDay};
-use crate::HoursMinSec;
+use crate::duration::Duration;
+use crate::julian_day::JulianDay;
+use crate::local_date::{LocalDate, UtcOffset};
+use crate::parse::parse_iso_date;
+use crate::{Error, HoursMinSec};
 
-#[derive(PartialEq)]
+/// Julian Day of 1582-10-15 0h, the first day of the Gregorian calendar.
+const GREGORIAN_REFORM_JD: f64 = 2_299_160.5;
+
+/// Largest number of decimals for the seconds of an ISO 8601 string, a
+/// nanosecond is already below the resolution of the `f64` day of a date.
+const MAX_ISO_PRECISION: usize = 9;
+
+/// Calendar in which the year, month and day of a [`Date`] are reckoned.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Calendar {
+    /// Julian calendar at all dates.
+    Julian,
+    /// Julian calendar until 1582-10-04 followed by the Gregorian calendar from
+    /// 1582-10-15, as in Meeus ch. 7.
+    #[default]
+    Gregorian,
+    /// Gregorian calendar extended to dates before its introduction.
+    ProlepticGregorian,
+    /// Julian calendar followed by the Gregorian calendar from the given Julian
+    /// Day, the 0h of the first Gregorian day (e.g. 2361221.5 for 1752-09-14 in
+    /// Great Britain), see [`Calendar::switchover`].
+    Switchover(JulianDay),
+}
+
+impl Calendar {
+    /// Creates a [`Calendar::Switchover`] adopting the Gregorian calendar on the
+    /// given Gregorian date.
+    #[must_use]
+    pub fn switchover(year: i16, month: u8, day: u8) -> Self {
+        let first_day =
+            Date::new(year, month, f64::from(day)).with_calendar(Self::ProlepticGregorian);
+        Self::Switchover(JulianDay::from_date(&first_day))
+    }
+
+    /// Julian Day of the 0h of the first Gregorian day.
+    fn first_gregorian_day(&self) -> f64 {
+        match self {
+            Self::Julian => f64::INFINITY,
+            Self::Gregorian => GREGORIAN_REFORM_JD,
+            Self::ProlepticGregorian => f64::NEG_INFINITY,
+            Self::Switchover(jd) => jd.get_value(),
+        }
+    }
+
+    /// Returns `true` if a Julian Day falls in the Gregorian part of the calendar.
+    pub(crate) fn is_gregorian(&self, jd: f64) -> bool {
+        jd >= self.first_gregorian_day()
+    }
+
+    /// Whole part of the Julian Day of a date, the Julian Day of the noon
+    /// preceding day 0 of the month, or `None` if the date was skipped at the
+    /// switchover (Meeus eq. 7.1).
+    pub(crate) fn month_base(&self, year: i16, month: u8, day: f64) -> Option<f64> {
+        let (julian, gregorian) = month_bases(i32::from(year), month);
+
+        if self.is_gregorian(gregorian + day.floor() + 0.5) {
+            Some(gregorian)
+        } else if !self.is_gregorian(julian + day.floor() + 0.5) {
+            Some(julian)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if February of `year` has 29 days, the Gregorian rule
+    /// applies if March 1st is in the Gregorian part of the calendar.
+    #[must_use]
+    pub fn is_leap_year(&self, year: i16) -> bool {
+        let (_, gregorian) = month_bases(i32::from(year), 3);
+
+        if self.is_gregorian(gregorian + 1.5) {
+            (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+        } else {
+            year % 4 == 0
+        }
+    }
+
+    /// Number of the last day of a month, days skipped at the switchover are
+    /// not subtracted (October 1582 ends on the 31st).
+    #[must_use]
+    pub fn days_in_month(&self, year: i16, month: u8) -> u8 {
+        match month {
+            4 | 6 | 9 | 11 => 30,
+            2 if self.is_leap_year(year) => 29,
+            2 => 28,
+            _ => 31,
+        }
+    }
+
+    /// Number of days of a year, 355 for 1582 in the [`Calendar::Gregorian`] calendar.
+    #[must_use]
+    pub fn days_in_year(&self, year: i16) -> u16 {
+        let year = i32::from(year);
+        (self.first_day_of_year(year + 1) - self.first_day_of_year(year)) as u16
+    }
+
+    /// Julian Day of the 0h of the first day of a year, the first Gregorian
+    /// day if January 1st was skipped at the switchover.
+    pub(crate) fn first_day_of_year(&self, year: i32) -> f64 {
+        let (julian, gregorian) = month_bases(year, 1);
+        if self.is_gregorian(gregorian + 1.5) {
+            gregorian + 1.5
+        } else {
+            (julian + 1.5).min(self.first_gregorian_day())
+        }
+    }
+
+    /// Returns `true` if the date exists in the calendar: the month is in the
+    /// range 1-12, the day (with its fraction) is within the month and was not
+    /// skipped at the switchover, e.g. 1582-10-10 in the [`Calendar::Gregorian`] calendar.
+    #[must_use]
+    pub fn is_valid_date(&self, year: i16, month: u8, day: f64) -> bool {
+        (1..=12).contains(&month)
+            && (1_f64..f64::from(self.days_in_month(year, month)) + 1_f64).contains(&day)
+            && self.month_base(year, month, day).is_some()
+    }
+}
+
+/// Whole parts of the Julian Day of day 0 of a month in the Julian and in the
+/// Gregorian calendar, `B` being 0 in the Julian calendar (Meeus eq. 7.1).
+fn month_bases(year: i32, month: u8) -> (f64, f64) {
+    let (y, m) = if month > 2 {
+        (year, i32::from(month))
+    } else {
+        (year - 1, i32::from(month) + 12)
+    };
+
+    let julian = (365.25_f64 * f64::from(y + 4716)).floor()
+        + (30.6001_f64 * f64::from(m + 1)).floor()
+        - 1525_f64;
+    let a = y.div_euclid(100);
+    (julian, julian + f64::from(2 - a + a.div_euclid(4)))
+}
+
+/// A calendar date and time of day, in the Gregorian calendar with the 1582
+/// switchover unless another [`Calendar`] is given.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Date {
     pub year: i16,
     pub month: u8,
     pub day: f64,
     pub hms: HoursMinSec,
+    pub calendar: Calendar,
 }
 
 impl Date {
     #[must_use]
     pub fn new(year: i16, month: u8, day: f64) -> Self {
-        Self {
-            year,
-            month,
-            day,
-            hms: HoursMinSec {
-                hours: 0,
-                minutes: 0,
-                seconds: 0.0,
-            },
-        }
+        Self::from_full_date(year, month, day, 0, 0, 0.0)
     }
 
     /// Create a `Date` object from values: year, month, day, seconds, minutes, seconds.
@@ -45,26 +179,332 @@ impl Date {
                 minutes,
                 seconds,
             },
+            calendar: Calendar::default(),
         }
     }
 
+    /// Returns the same date reckoned in the given calendar.
+    ///
+    /// This doesn't convert the date, see [`Date::to_calendar`].
+    #[must_use]
+    pub fn with_calendar(self, calendar: Calendar) -> Self {
+        Self { calendar, ..self }
+    }
+
+    /// Converts the date to the given calendar, e.g. 1582-10-15 Gregorian is
+    /// 1582-10-05 in the Julian calendar.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date is invalid, see [`Date::try_to_calendar`].
+    #[must_use]
+    pub fn to_calendar(&self, calendar: Calendar) -> Self {
+        self.try_to_calendar(calendar).expect("Invalid date")
+    }
+
+    /// Fallible version of [`Date::to_calendar`].
+    pub fn try_to_calendar(&self, calendar: Calendar) -> Result<Self, Error> {
+        let day = Self::new(self.year, self.month, self.day.floor()).with_calendar(self.calendar);
+        let converted = day
+            .try_to_julian_day()?
+            .try_to_calendar_date_with(calendar)?;
+        Ok(Self {
+            day: converted.day + self.day.fract(),
+            hms: self.hms,
+            ..converted
+        })
+    }
+
+    /// Creates a date from a Julian Day, with a whole `day` and the time of day
+    /// in `hms` rounded to the microsecond.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the year doesn't fit an `i16`, see [`Date::try_from_julian_day`].
+    #[must_use]
+    pub fn from_julian_day(jd: &JulianDay) -> Self {
+        Self::from_julian_day_with(jd, Calendar::default())
+    }
+
+    /// Fallible version of [`Date::from_julian_day`], returning
+    /// [`Error::YearOutOfRange`] if the year doesn't fit an `i16`.
+    pub fn try_from_julian_day(jd: &JulianDay) -> Result<Self, Error> {
+        Self::try_from_julian_day_with(jd, Calendar::default())
+    }
+
+    /// Creates a date in the given calendar from a Julian Day, see [`Date::from_julian_day`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the year doesn't fit an `i16`, see [`Date::try_from_julian_day_with`].
+    #[must_use]
+    pub fn from_julian_day_with(jd: &JulianDay, calendar: Calendar) -> Self {
+        Self::try_from_julian_day_with(jd, calendar).expect("Year out of range")
+    }
+
+    /// Fallible version of [`Date::from_julian_day_with`].
+    pub fn try_from_julian_day_with(jd: &JulianDay, calendar: Calendar) -> Result<Self, Error> {
+        let jd = JulianDay::from_parts(jd.day(), (jd.fraction() * 86_400e6).round() / 86_400e6);
+        let date = jd.try_to_calendar_date_with(calendar)?;
+        let day = date.day.floor();
+        let seconds = ((date.day - day) * 86_400e6).round() / 1e6;
+        let hours = (seconds / 3600_f64).floor();
+        let minutes = ((seconds - hours * 3600_f64) / 60_f64).floor();
+
+        Ok(Self::from_full_date(
+            date.year,
+            date.month,
+            day,
+            hours as u8,
+            minutes as u8,
+            seconds - hours * 3600_f64 - minutes * 60_f64,
+        )
+        .with_calendar(calendar))
+    }
+
+    /// Creates a date from a year and the number of the day in the year,
+    /// 1 being January 1st (Meeus ch. 7, p. 66).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the year doesn't have that many days, see [`Date::try_from_year_day`].
+    #[must_use]
+    pub fn from_year_day(year: i16, year_day: u16, calendar: Calendar) -> Self {
+        Self::try_from_year_day(year, year_day, calendar).expect("Invalid day of the year")
+    }
+
+    /// Fallible version of [`Date::from_year_day`], returning
+    /// [`Error::InvalidDayOfYear`] if the day is not in the range 1 to the
+    /// number of days of the year.
+    pub fn try_from_year_day(year: i16, year_day: u16, calendar: Calendar) -> Result<Self, Error> {
+        if year_day == 0 || year_day > calendar.days_in_year(year) {
+            return Err(Error::InvalidDayOfYear(year_day));
+        }
+
+        let first_day = JulianDay::new(calendar.first_day_of_year(i32::from(year)));
+        (first_day + f64::from(year_day - 1)).try_to_calendar_date_with(calendar)
+    }
+
+    /// Parses an ISO 8601 / RFC 3339 date or a FITS `DATE-OBS` value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string cannot be parsed, see [`Date::try_from_string`].
+    #[must_use]
+    pub fn from_string(s: &str) -> Self {
+        Self::try_from_string(s).expect("Cannot parse date string")
+    }
+
+    /// Parses an ISO 8601 / RFC 3339 date or a FITS `DATE-OBS` value, returning
+    /// [`Error::InvalidDate`] if the string cannot be parsed.
+    ///
+    /// Accepted notations include `2000-06-17`, `2000-06-17T08:34:57.25`,
+    /// `2000-06-17T08:34:57Z`, `2000-06-17 10:34+02:00`, expanded years such as
+    /// `-0044-03-15` or `+12000-01-01`, and the old FITS form `17/06/98`. A date
+    /// without offset is read as UTC, a date with an offset is converted to UTC.
+    pub fn try_from_string(s: &str) -> Result<Self, Error> {
+        let parsed = parse_iso_date(s).map_err(Error::InvalidDate)?;
+        let date = Self::from_full_date(
+            parsed.year,
+            parsed.month,
+            f64::from(parsed.day),
+            parsed.hours,
+            parsed.minutes,
+            parsed.seconds,
+        );
+
+        match parsed.offset {
+            None | Some(0) => Ok(date),
+            Some(offset) => Ok(LocalDate::new(date, UtcOffset::from_seconds(offset)).to_utc()),
+        }
+    }
+
+    /// Formats the date as a FITS `DATE-OBS` value, `YYYY-MM-DDThh:mm:ss`
+    /// without time zone and with `precision` decimals for the seconds, at most
+    /// 9; larger values are clamped.
+    #[must_use]
+    pub fn to_fits_string(&self, precision: usize) -> String {
+        let mut s = String::new();
+        self.write_iso(&mut s, precision)
+            .expect("writing to a String cannot fail");
+        s
+    }
+
+    /// Writes the date and time of day in the ISO 8601 extended format without
+    /// time zone. The fraction of `day` is added to the time of day, and the
+    /// seconds are rounded to `precision` decimals (at most 9) carrying into the next day.
+    pub(crate) fn write_iso(&self, f: &mut impl Write, precision: usize) -> fmt::Result {
+        let precision = precision.min(MAX_ISO_PRECISION);
+        let scale = 10_u64.pow(precision as u32);
+        let day_scale = 86_400 * scale;
+        let whole_day = self.day.floor();
+        let seconds = (self.day - whole_day) * 86_400_f64
+            + f64::from(self.hms.hours) * 3600_f64
+            + f64::from(self.hms.minutes) * 60_f64
+            + self.hms.seconds;
+
+        let mut date = Self::new(self.year, self.month, whole_day).with_calendar(self.calendar);
+        let mut total = (seconds * scale as f64).round() as u64;
+        if total >= day_scale {
+            let next_day = date.try_to_julian_day().and_then(|jd| {
+                Self::try_from_julian_day_with(&(jd + (total / day_scale) as f64), self.calendar)
+            });
+            if let Ok(next_day) = next_day {
+                date = next_day;
+                total %= day_scale;
+            }
+        }
+
+        if (0..=9999).contains(&date.year) {
+            write!(f, "{:04}", date.year)?;
+        } else {
+            write!(f, "{:+05}", date.year)?;
+        }
+
+        let secs = total / scale;
+        write!(
+            f,
+            "-{:02}-{:02}T{:02}:{:02}:{:02}",
+            date.month,
+            date.day as u8,
+            secs / 3600,
+            secs % 3600 / 60,
+            secs % 60
+        )?;
+        if precision > 0 {
+            write!(f, ".{:0precision$}", total % scale)?;
+        }
+        Ok(())
+    }
+
+    /// Creates a UTC date from a Unix time, see [`JulianDay::from_unix`].
+    #[must_use]
+    pub fn from_unix(seconds: f64) -> Self {
+        Self::from_julian_day(&JulianDay::from_unix(seconds))
+    }
+
+    /// Returns the Unix time of a UTC date.
+    #[must_use]
+    pub fn to_unix(&self) -> f64 {
+        self.to_julian_day().to_unix()
+    }
+
+    /// Creates a UTC date from a [`SystemTime`].
+    #[must_use]
+    pub fn from_system_time(time: SystemTime) -> Self {
+        Self::from_julian_day(&JulianDay::from_system_time(time))
+    }
+
+    /// UTC date of the system clock.
+    #[must_use]
+    pub fn now() -> Self {
+        Self::from_system_time(SystemTime::now())
+    }
+
     #[must_use]
     pub fn to_julian_day(&self) -> JulianDay {
-        let jd = get_julian_day(self);
-        JulianDay::new(jd)
+        JulianDay::from_date(self)
     }
 
-    /// Returns the days interval between two dates
+    /// Fallible version of [`Date::to_julian_day`].
+    pub fn try_to_julian_day(&self) -> Result<JulianDay, Error> {
+        JulianDay::try_from_date(self)
+    }
+
+    /// Returns the same instant with the fraction of `day` and any overflow of
+    /// `hms` (e.g. 25:61:00) carried into the date, the time of day being
+    /// rounded to the microsecond.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date is invalid, see [`Date::try_normalized`].
     #[must_use]
-    pub fn interval(&self, other: &Self) -> f64 {
-        let self_jd = self.to_julian_day();
-        let other_jd = other.to_julian_day();
+    pub fn normalized(&self) -> Self {
+        self.try_normalized().expect("Invalid date")
+    }
+
+    /// Fallible version of [`Date::normalized`].
+    pub fn try_normalized(&self) -> Result<Self, Error> {
+        Self::try_from_julian_day_with(&self.try_to_julian_day()?, self.calendar)
+    }
+
+    /// Adds a number of calendar months, keeping the day of the month and the
+    /// time of day. The day is clamped to the end of a shorter month, so
+    /// January 31st plus one month is the last day of February.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date is invalid, the year of the result doesn't fit an
+    /// `i16` or the result falls in the days skipped at the switchover of the
+    /// calendar, see [`Date::try_add_months`].
+    #[must_use]
+    pub fn add_months(&self, months: i32) -> Self {
+        self.try_add_months(months).expect("Invalid date")
+    }
+
+    /// Fallible version of [`Date::add_months`], returning
+    /// [`Error::YearOutOfRange`] if the year of the result doesn't fit an `i16`.
+    pub fn try_add_months(&self, months: i32) -> Result<Self, Error> {
+        self.try_add_total_months(i64::from(months))
+    }
+
+    fn try_add_total_months(&self, months: i64) -> Result<Self, Error> {
+        self.try_to_julian_day()?;
 
-        if self_jd > other_jd {
-            self_jd.get_value() - other_jd.get_value()
+        let total = i64::from(self.year) * 12 + i64::from(self.month) - 1 + months;
+        let year = total.div_euclid(12);
+        let year = i16::try_from(year).map_err(|_| {
+            Error::YearOutOfRange(year.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32)
+        })?;
+        let month = total.rem_euclid(12) as u8 + 1;
+        let last_day = f64::from(self.calendar.days_in_month(year, month));
+        let day = if self.day.floor() > last_day {
+            last_day + self.day.fract()
         } else {
-            other_jd.get_value() - self_jd.get_value()
+            self.day
+        };
+
+        let date = Self {
+            year,
+            month,
+            day,
+            ..*self
+        };
+        if !date.is_valid() {
+            return Err(Error::InvalidDay(day));
         }
+        Ok(date)
+    }
+
+    /// Adds a number of calendar years, February 29th becomes February 28th
+    /// in common years, see [`Date::add_months`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date is invalid or the year of the result doesn't fit an
+    /// `i16`, see [`Date::try_add_years`].
+    #[must_use]
+    pub fn add_years(&self, years: i32) -> Self {
+        self.try_add_years(years).expect("Invalid date")
+    }
+
+    /// Fallible version of [`Date::add_years`], see [`Date::try_add_months`].
+    pub fn try_add_years(&self, years: i32) -> Result<Self, Error> {
+        self.try_add_total_months(i64::from(years) * 12)
+    }
+
+    /// Fallible version of `Date + Duration`, returning [`Error::YearOutOfRange`]
+    /// if the year of the result doesn't fit an `i16`.
+    pub fn try_add(&self, duration: Duration) -> Result<Self, Error> {
+        Self::try_from_julian_day_with(&(self.try_to_julian_day()? + duration), self.calendar)
+    }
+
+    /// Returns the days interval between two dates, see `Sub` for a signed [`Duration`].
+    #[must_use]
+    pub fn interval(&self, other: &Self) -> f64 {
+        self.to_julian_day()
+            .days_since(&other.to_julian_day())
+            .abs()
     }
 
     /// Returns the day of the week of a calendar date (1 is Monday, 7 is Sunday)
@@ -74,47 +514,92 @@ impl Date {
         ((jd.get_value() + 1.5_f64) as i32 % 7) as u8
     }
 
+    /// Returns `true` if the year of the date is a leap year in its calendar.
     #[must_use]
     pub fn is_leap(&self) -> bool {
-        false
+        self.calendar.is_leap_year(self.year)
     }
 
+    /// Returns `true` if the date exists in its calendar, see [`Calendar::is_valid_date`].
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.calendar.is_valid_date(self.year, self.month, self.day)
+    }
+
+    /// Returns the number of the day in the year, 1 being January 1st (Meeus ch. 7, p. 65).
+    ///
+    /// Days skipped at the switchover are not counted, 1582-10-15 is day 278.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date is invalid.
     #[must_use]
     pub fn year_day(&self) -> u16 {
-        // todo implement is_leap()
-        let k = {
-            if self.is_leap() {
-                1
-            } else {
-                2
-            }
-        };
+        let day = Self::new(self.year, self.month, self.day.floor()).with_calendar(self.calendar);
+        let first_day = JulianDay::new(self.calendar.first_day_of_year(i32::from(self.year)));
+        (day.to_julian_day().days_since(&first_day) + 1_f64) as u16
+    }
+}
 
-        (f64::from(275_u16 * u16::from(self.month)) / 9.0_f64) as u16
-            - k * (f64::from(self.month + 9) / 12.0_f64) as u16
-            + (self.day as u16)
-            - 30
+impl Add<Duration> for Date {
+    type Output = Self;
+
+    /// Adds a span of time, the result is in the same calendar with the time
+    /// of day rounded to the microsecond.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date is invalid or the year of the result doesn't fit an
+    /// `i16`, see [`Date::try_add`].
+    fn add(self, duration: Duration) -> Self {
+        self.try_add(duration).expect("Invalid date")
     }
 }
 
+impl Sub<Duration> for Date {
+    type Output = Self;
+
+    /// Subtracts a span of time, see `Add`.
+    fn sub(self, duration: Duration) -> Self {
+        self + -duration
+    }
+}
+
+impl Sub for Date {
+    type Output = Duration;
+
+    /// Returns the signed span from `other` to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either date is invalid.
+    fn sub(self, other: Self) -> Duration {
+        self.to_julian_day() - other.to_julian_day()
+    }
+}
+
+impl FromStr for Date {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_string(s)
+    }
+}
+
+/// Formats the date as RFC 3339 in UTC with microseconds, e.g. `2000-06-17T08:34:57.000000Z`.
 impl Display for Date {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(
-            f,
-            "{}-{}-{} {}:{}:{:.6}Z",
-            self.year,
-            self.month,
-            self.day as u8,
-            self.hms.hours,
-            self.hms.minutes,
-            self.hms.seconds
-        )
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.write_iso(f, 6)?;
+        f.write_char('Z')
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::date::Date;
+    use crate::date::{Calendar, Date};
+    use crate::duration::Duration;
+    use crate::julian_day::JulianDay;
+    use crate::Error;
 
     #[test]
     fn test_date_to_julian_date() {
@@ -149,9 +634,338 @@ mod test {
         assert_eq!(date.year_day(), 318);
     }
 
+    #[test]
+    fn test_day_of_the_year_leap_year() {
+        // From Meeus book "astronomical algorithms" p. 65 example 7.g
+        let date = Date::new(1988, 4, 22.0);
+        assert!(date.is_leap());
+        assert_eq!(date.year_day(), 113);
+        assert_eq!(Date::from_year_day(1988, 113, Calendar::default()), date);
+        assert_eq!(
+            Date::from_year_day(1978, 318, Calendar::default()),
+            Date::new(1978, 11, 14.0)
+        );
+        assert_eq!(Date::new(2000, 12, 31.0).year_day(), 366);
+        assert_eq!(
+            Date::try_from_year_day(1978, 366, Calendar::default()),
+            Err(Error::InvalidDayOfYear(366))
+        );
+    }
+
+    #[test]
+    fn test_leap_years() {
+        // From Meeus book "astronomical algorithms" p. 62
+        let calendar = Calendar::default();
+        for year in [900, 1236, 1600, 2000, 2400] {
+            assert!(calendar.is_leap_year(year), "{year}");
+        }
+        for year in [750, 1429, 1700, 1800, 1900, 2100] {
+            assert!(!calendar.is_leap_year(year), "{year}");
+        }
+        assert!(Calendar::Julian.is_leap_year(1900));
+        assert!(Calendar::Julian.is_leap_year(200));
+        assert!(!Calendar::ProlepticGregorian.is_leap_year(900));
+        assert!(Calendar::ProlepticGregorian.is_leap_year(-400));
+        assert_eq!(calendar.days_in_month(2024, 2), 29);
+        assert_eq!(calendar.days_in_month(2023, 2), 28);
+    }
+
+    #[test]
+    fn test_gregorian_switchover() {
+        let calendar = Calendar::default();
+        assert_eq!(calendar.days_in_year(1582), 355);
+        assert_eq!(Date::new(1582, 10, 15.0).year_day(), 278);
+        assert_eq!(Date::new(1582, 10, 4.0).year_day(), 277);
+        assert!(!Date::new(1582, 10, 10.0).is_valid());
+        assert!(Date::new(1582, 10, 10.0)
+            .with_calendar(Calendar::Julian)
+            .is_valid());
+        assert_eq!(
+            Date::new(1582, 10, 10.0).try_to_julian_day(),
+            Err(Error::InvalidDay(10.0))
+        );
+        assert_eq!(
+            Date::new(1582, 10, 15.0).to_calendar(Calendar::Julian),
+            Date::new(1582, 10, 5.0).with_calendar(Calendar::Julian)
+        );
+
+        // Great Britain went from 1752-09-02 to 1752-09-14
+        let britain = Calendar::switchover(1752, 9, 14);
+        assert_eq!(britain, Calendar::Switchover(JulianDay::new(2_361_221.5)));
+        assert!(britain.is_leap_year(1752));
+        assert_eq!(britain.days_in_year(1752), 355);
+        assert!(!Date::new(1752, 9, 3.0).with_calendar(britain).is_valid());
+        let before = Date::new(1752, 9, 2.0).with_calendar(britain);
+        assert_eq!(
+            Date::from_julian_day_with(&(before.to_julian_day() + 1_f64), britain),
+            Date::new(1752, 9, 14.0).with_calendar(britain)
+        );
+
+        // a switchover skipping January 1st, the year starts on the first Gregorian day
+        let january = Calendar::switchover(1700, 1, 11);
+        assert_eq!(january.days_in_year(1699), 365);
+        assert_eq!(january.days_in_year(1700), 355);
+        assert_eq!(
+            Date::from_year_day(1700, 1, january),
+            Date::new(1700, 1, 11.0).with_calendar(january)
+        );
+        assert_eq!(
+            Date::new(1700, 12, 31.0).with_calendar(january).year_day(),
+            355
+        );
+    }
+
+    #[test]
+    fn test_days_in_first_and_last_years() {
+        assert_eq!(Calendar::default().days_in_year(i16::MAX), 365);
+        assert_eq!(Calendar::Julian.days_in_year(i16::MIN), 366);
+        assert_eq!(
+            Date::from_year_day(i16::MAX, 365, Calendar::default()),
+            Date::new(i16::MAX, 12, 31.0)
+        );
+    }
+
+    #[test]
+    fn test_invalid_dates() {
+        for (year, month, day) in [
+            (2023, 2, 29.0),
+            (2024, 2, 30.0),
+            (2024, 4, 31.0),
+            (2024, 1, 0.0),
+            (2024, 1, 32.0),
+        ] {
+            let date = Date::new(year, month, day);
+            assert!(!date.is_valid(), "{year}-{month}-{day}");
+            assert_eq!(date.try_to_julian_day(), Err(Error::InvalidDay(day)));
+        }
+        assert!(Date::new(2024, 2, 29.9).is_valid());
+    }
+
+    #[test]
+    fn test_date_duration_arithmetic() {
+        // From Meeus book "astronomical algorithms" p.64 example 7.d
+        let halley = Date::new(1910, 4, 20.0);
+        let later = Date::new(1986, 2, 9.0);
+        assert_eq!(later - halley, Duration::from_days(27_689.0));
+        assert_eq!(
+            halley + Duration::from_days(10_000.0),
+            Date::new(1937, 9, 5.0)
+        );
+        assert_eq!(later - Duration::from_days(27_689.0), halley);
+
+        let evening = Date::from_full_date(2024, 2, 28.0, 21, 30, 0.0);
+        assert_eq!(
+            evening + Duration::from_hours(3.5),
+            Date::from_full_date(2024, 2, 29.0, 1, 0, 0.0)
+        );
+        assert!((halley - later).is_negative());
+
+        let julian = Date::new(1582, 10, 4.0).with_calendar(Calendar::Julian);
+        assert_eq!(
+            julian + Duration::from_days(1.0),
+            Date::new(1582, 10, 5.0).with_calendar(Calendar::Julian)
+        );
+        assert_eq!(
+            Date::new(1582, 10, 4.0) + Duration::from_days(1.0),
+            Date::new(1582, 10, 15.0)
+        );
+
+        let date = Date::new(2024, 1, 1.0);
+        assert!(matches!(
+            date.try_add(Duration::from_days(1e9)),
+            Err(Error::YearOutOfRange(_))
+        ));
+        assert_eq!(
+            date.try_add(Duration::from_days(366.0)),
+            Ok(Date::new(2025, 1, 1.0))
+        );
+    }
+
+    #[test]
+    fn test_date_normalized() {
+        let date = Date::from_full_date(2023, 12, 31.0, 25, 61, 75.5);
+        assert_eq!(
+            date.normalized(),
+            Date::from_full_date(2024, 1, 1.0, 2, 2, 15.5)
+        );
+        assert_eq!(
+            Date::new(1957, 10, 4.81).normalized(),
+            Date::from_full_date(1957, 10, 4.0, 19, 26, 24.0)
+        );
+        assert!(Date::new(2023, 2, 29.0).try_normalized().is_err());
+    }
+
+    #[test]
+    fn test_add_months() {
+        let date = Date::from_full_date(2024, 1, 31.0, 22, 0, 0.0);
+        assert_eq!(
+            date.add_months(1),
+            Date::from_full_date(2024, 2, 29.0, 22, 0, 0.0)
+        );
+        assert_eq!(
+            date.add_months(13),
+            Date::from_full_date(2025, 2, 28.0, 22, 0, 0.0)
+        );
+        assert_eq!(
+            date.add_months(-2),
+            Date::from_full_date(2023, 11, 30.0, 22, 0, 0.0)
+        );
+        assert_eq!(
+            Date::new(2024, 2, 29.0).add_years(1),
+            Date::new(2025, 2, 28.0)
+        );
+        assert_eq!(
+            Date::new(2024, 2, 29.0).add_years(-4),
+            Date::new(2020, 2, 29.0)
+        );
+        assert_eq!(Date::new(1, 3, 1.5).add_months(-3), Date::new(0, 12, 1.5));
+
+        assert_eq!(
+            Date::new(1582, 9, 10.0).try_add_months(1),
+            Err(Error::InvalidDay(10.0))
+        );
+        assert!(Date::new(2024, 2, 30.0).try_add_months(1).is_err());
+
+        // years outside the i16 range are errors, not wrapped or overflowing
+        let date = Date::new(2024, 1, 1.0);
+        assert_eq!(
+            date.try_add_years(40_000),
+            Err(Error::YearOutOfRange(42_024))
+        );
+        assert_eq!(
+            date.try_add_years(i32::MIN),
+            Err(Error::YearOutOfRange(i32::MIN + 2024))
+        );
+        assert_eq!(
+            date.try_add_months(i32::MAX),
+            Err(Error::YearOutOfRange(178_958_994))
+        );
+        assert_eq!(
+            date.add_years(i32::from(i16::MAX) - 2024),
+            Date::new(i16::MAX, 1, 1.0)
+        );
+    }
+
     #[test]
     fn test_date_with_hours_format() {
         let date = Date::from_full_date(2000, 6, 17.0, 8, 34, 57.0);
-        assert_eq!(format!("{}", date), "2000-6-17 8:34:57.000000Z");
+        assert_eq!(format!("{}", date), "2000-06-17T08:34:57.000000Z");
+        assert_eq!(date.to_fits_string(3), "2000-06-17T08:34:57.000");
+    }
+
+    #[test]
+    fn test_date_format_rounding_and_years() {
+        // From Meeus book "astronomical algorithms" p. 64 example 7.c, 4.81 is 19h26m24s
+        let date = Date::new(1957, 10, 4.81);
+        assert_eq!(date.to_fits_string(0), "1957-10-04T19:26:24");
+
+        let date = Date::from_full_date(1999, 12, 31.0, 23, 59, 59.999_999_9);
+        assert_eq!(date.to_string(), "2000-01-01T00:00:00.000000Z");
+        assert_eq!(date.to_fits_string(9), "1999-12-31T23:59:59.999999900");
+        assert_eq!(date.to_fits_string(15), date.to_fits_string(9));
+        assert_eq!(date.to_fits_string(usize::MAX), date.to_fits_string(9));
+
+        // rounding into the next day stays in the calendar of the date
+        let julian = Date::from_full_date(1900, 2, 28.0, 23, 59, 59.999_999_9)
+            .with_calendar(Calendar::Julian);
+        assert_eq!(julian.to_fits_string(3), "1900-02-29T00:00:00.000");
+        let switchover = Date::from_full_date(1752, 9, 2.0, 23, 59, 59.999_9)
+            .with_calendar(Calendar::switchover(1752, 9, 14));
+        assert_eq!(switchover.to_fits_string(3), "1752-09-14T00:00:00.000");
+
+        assert_eq!(
+            Date::new(-44, 3, 15.0).to_fits_string(0),
+            "-0044-03-15T00:00:00"
+        );
+        assert_eq!(
+            Date::new(12000, 1, 1.0).to_fits_string(0),
+            "+12000-01-01T00:00:00"
+        );
+        assert_eq!(
+            Date::new(800, 12, 25.0).to_fits_string(0),
+            "0800-12-25T00:00:00"
+        );
+    }
+
+    #[test]
+    fn test_date_parse() {
+        let expected = Date::from_full_date(2000, 6, 17.0, 8, 34, 57.25);
+        for s in [
+            "2000-06-17T08:34:57.25Z",
+            "2000-06-17T08:34:57.250",
+            "2000-06-17t08:34:57,25z",
+            "2000-06-17 10:34:57.25+02:00",
+            "2000-06-17T03:04:57.25-05:30",
+        ] {
+            assert_eq!(s.parse::<Date>().unwrap(), expected, "{s}");
+        }
+
+        assert_eq!(Date::from_string("2000-06-17"), Date::new(2000, 6, 17.0));
+        assert_eq!(Date::from_string("17/06/98"), Date::new(1998, 6, 17.0));
+        assert_eq!(Date::from_string("-0044-03-15"), Date::new(-44, 3, 15.0));
+        assert_eq!(Date::from_string("+12000-01-01"), Date::new(12000, 1, 1.0));
+        assert_eq!(Date::from_string("2016-12-31T23:59:60Z").hms.seconds, 60.0);
+        // crossing midnight and the year because of the offset
+        assert_eq!(
+            Date::from_string("2000-01-01T01:00+02:00"),
+            Date::from_full_date(1999, 12, 31.0, 23, 0, 0.0)
+        );
+
+        for date in [
+            Date::from_full_date(1957, 10, 4.0, 19, 26, 24.5),
+            Date::new(-4712, 1, 1.0),
+        ] {
+            assert_eq!(date.to_string().parse::<Date>().unwrap(), date);
+        }
+    }
+
+    #[test]
+    fn test_date_parse_errors() {
+        for s in [
+            "",
+            "2000-6-17",
+            "2000-06-17T8:34",
+            "2000-06-17T08:34:57.",
+            "2000-06-17T08:34:57Zx",
+            "12000-01-01",
+            "2001-02-29",
+            "1900-02-29",
+            "2000-13-01",
+            "2000-06-17T24:00:00",
+            "2000-06-17T08:34:61",
+            "31/04/98",
+            "1582-10-10",
+        ] {
+            assert!(
+                matches!(s.parse::<Date>(), Err(Error::InvalidDate(_))),
+                "{s}"
+            );
+        }
+        // Julian leap year
+        assert!("1500-02-29".parse::<Date>().is_ok());
+    }
+
+    #[test]
+    fn test_date_from_unix() {
+        // 2009-02-13T23:31:30Z
+        let date = Date::from_unix(1_234_567_890.0);
+        assert_eq!((date.year, date.month, date.day), (2009, 2, 13.0));
+        assert_eq!((date.hms.hours, date.hms.minutes), (23, 31));
+        assert!((date.hms.seconds - 30.0).abs() < 1e-6);
+        assert!((date.to_unix() - 1_234_567_890.0).abs() < 1e-6);
+
+        let epoch = Date::from_unix(0.0);
+        assert_eq!(epoch, Date::new(1970, 1, 1.0));
+        assert_eq!(epoch.to_unix(), 0.0);
+    }
+
+    #[test]
+    fn test_date_from_julian_day() {
+        // From Meeus book "astronomical algorithms" p. 64 example 7.c
+        let date = Date::from_julian_day(&JulianDay::new(2_436_116.31));
+        assert_eq!((date.year, date.month, date.day), (1957, 10, 4.0));
+        assert_eq!((date.hms.hours, date.hms.minutes), (19, 26));
+        assert!((date.hms.seconds - 24.0).abs() < 1e-3);
+        assert!(Date::now().year >= 2024);
     }
 }
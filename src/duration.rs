@@ -0,0 +1,186 @@
+//! Signed time spans between dates and Julian Days.
+//!
+//! A [`Duration`] can be added to or subtracted from a [`JulianDay`](crate::julian_day::JulianDay),
+//! a [`Date`](crate::date::Date) or an [`Instant`](crate::time_scale::Instant), and is
+//! the result of subtracting two of them.
+
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+const SECONDS_PER_DAY: f64 = 86_400_f64;
+
+/// A signed span of time with a resolution of seconds, negative spans go
+/// backwards in time.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Duration {
+    seconds: f64,
+}
+
+impl Duration {
+    /// The empty span.
+    pub const ZERO: Self = Self { seconds: 0_f64 };
+
+    #[must_use]
+    pub fn from_seconds(seconds: f64) -> Self {
+        Self { seconds }
+    }
+
+    #[must_use]
+    pub fn from_minutes(minutes: f64) -> Self {
+        Self::from_seconds(minutes * 60_f64)
+    }
+
+    #[must_use]
+    pub fn from_hours(hours: f64) -> Self {
+        Self::from_seconds(hours * 3600_f64)
+    }
+
+    #[must_use]
+    pub fn from_days(days: f64) -> Self {
+        Self::from_seconds(days * SECONDS_PER_DAY)
+    }
+
+    /// Creates a span of 7 days per week.
+    #[must_use]
+    pub fn from_weeks(weeks: f64) -> Self {
+        Self::from_days(weeks * 7_f64)
+    }
+
+    #[must_use]
+    pub fn seconds(&self) -> f64 {
+        self.seconds
+    }
+
+    #[must_use]
+    pub fn minutes(&self) -> f64 {
+        self.seconds / 60_f64
+    }
+
+    #[must_use]
+    pub fn hours(&self) -> f64 {
+        self.seconds / 3600_f64
+    }
+
+    #[must_use]
+    pub fn days(&self) -> f64 {
+        self.seconds / SECONDS_PER_DAY
+    }
+
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.seconds < 0_f64
+    }
+
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self::from_seconds(self.seconds.abs())
+    }
+}
+
+impl From<std::time::Duration> for Duration {
+    fn from(duration: std::time::Duration) -> Self {
+        Self::from_seconds(duration.as_secs_f64())
+    }
+}
+
+impl Add for Duration {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::from_seconds(self.seconds + other.seconds)
+    }
+}
+
+impl Sub for Duration {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::from_seconds(self.seconds - other.seconds)
+    }
+}
+
+impl Neg for Duration {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_seconds(-self.seconds)
+    }
+}
+
+impl Mul<f64> for Duration {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        Self::from_seconds(self.seconds * factor)
+    }
+}
+
+impl Div<f64> for Duration {
+    type Output = Self;
+
+    fn div(self, divisor: f64) -> Self {
+        Self::from_seconds(self.seconds / divisor)
+    }
+}
+
+/// Formats the span as `[-][Nd ]hh:mm:ss.sss`, e.g. `-1d 02:03:04.500`.
+impl Display for Duration {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let millis = (self.seconds.abs() * 1000_f64).round() as u64;
+        let secs = millis / 1000;
+        let days = secs / 86_400;
+
+        if self.is_negative() && millis > 0 {
+            write!(f, "-")?;
+        }
+        if days > 0 {
+            write!(f, "{days}d ")?;
+        }
+        write!(
+            f,
+            "{:02}:{:02}:{:02}.{:03}",
+            secs % 86_400 / 3600,
+            secs % 3600 / 60,
+            secs % 60,
+            millis % 1000
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_duration_units() {
+        let span = Duration::from_hours(36.0);
+        assert_eq!(span.days(), 1.5);
+        assert_eq!(span.minutes(), 2160.0);
+        assert_eq!(span.seconds(), 129_600.0);
+        assert_eq!(Duration::from_weeks(1.0), Duration::from_days(7.0));
+        assert_eq!(
+            Duration::from(std::time::Duration::from_millis(1500)),
+            Duration::from_seconds(1.5)
+        );
+    }
+
+    #[test]
+    fn test_duration_arithmetic() {
+        let hour = Duration::from_hours(1.0);
+        let span = Duration::from_minutes(30.0) - hour;
+        assert!(span.is_negative());
+        assert_eq!(span, -Duration::from_minutes(30.0));
+        assert_eq!(span.abs() * 2.0, hour);
+        assert_eq!(hour / 4.0 + hour / 4.0, Duration::from_minutes(30.0));
+        assert!(Duration::ZERO < hour);
+    }
+
+    #[test]
+    fn test_duration_display() {
+        let span = Duration::from_days(1.0) + Duration::from_seconds(7384.5);
+        assert_eq!(span.to_string(), "1d 02:03:04.500");
+        assert_eq!((-span).to_string(), "-1d 02:03:04.500");
+        assert_eq!(Duration::from_minutes(-1.5).to_string(), "-00:01:30.000");
+        assert_eq!(Duration::ZERO.to_string(), "00:00:00.000");
+    }
+}
@@ -1,22 +1,62 @@
+use std::path::Path;
+
 use crate::date::Date;
+use crate::Error;
 
+/// Returns ΔT = TD - UT in seconds for the given date.
+///
+/// # Panics
+///
+/// Panics if the date cannot be converted to a decimal year, see [`try_delta_t`].
 #[must_use]
 pub fn delta_t(date: &Date) -> f64 {
-    // We define the decimal year "y" as follows:
+    try_delta_t(date).expect("Not supported")
+}
 
-    let decimal_year: f64 = f64::from(date.year) + (f64::from(date.month) - 0.5) / 12_f64;
+/// Returns ΔT = TD - UT in seconds for the given date, see [`delta_t_for_year`].
+///
+/// Returns [`Error::InvalidMonth`] if the month of `date` is invalid, or
+/// [`Error::DeltaTOutOfRange`] if the date does not give a finite decimal year.
+pub fn try_delta_t(date: &Date) -> Result<f64, Error> {
+    let decimal_year = try_get_decimal_year(date)?;
 
-    // This gives "y" for the middle of the month, which is accurate enough given the precision in
-    // the known values of ΔT. The following polynomial expressions can be used calculate the value
-    // of ΔT (in seconds) over the time period covered by of the Five Millennium Canon of Solar Eclipses:
-    // -1999 to +3000.
+    if !decimal_year.is_finite() {
+        return Err(Error::DeltaTOutOfRange(decimal_year));
+    }
 
-    let res = match decimal_year as i16 {
-        // Before the year -500, calculate:
-        -1999..=-501 => {
-            let u = (decimal_year - 1820_f64) / 100_f64;
-            -20_f64 + 32_f64 * (u * u)
-        }
+    Ok(delta_t_for_year(decimal_year))
+}
+
+/// Returns the decimal year of a date to the resolution of its day and time,
+/// e.g. 2000.5 is 2000 July 2 at 0h.
+///
+/// # Panics
+///
+/// Panics if the month of `date` is invalid, see [`try_get_decimal_year`].
+#[must_use]
+pub fn get_decimal_year(date: &Date) -> f64 {
+    try_get_decimal_year(date).expect("Error")
+}
+
+/// Fallible version of [`get_decimal_year`].
+pub fn try_get_decimal_year(date: &Date) -> Result<f64, Error> {
+    let jd = date.try_to_julian_day()?.get_value();
+    let new_year = Date::new(date.year, 1, 1.0)
+        .with_calendar(date.calendar)
+        .try_to_julian_day()?
+        .get_value();
+
+    Ok(f64::from(date.year) + (jd - new_year) / 365.25)
+}
+
+/// Returns ΔT = TD - UT in seconds for a decimal year.
+///
+/// Between -500 and +2150 the polynomial expressions of Espenak and Meeus
+/// (Five Millennium Canon of Solar Eclipses) are used, outside that range the
+/// long-term parabola of Morrison and Stephenson (2004).
+#[must_use]
+pub fn delta_t_for_year(decimal_year: f64) -> f64 {
+    match decimal_year.floor() as i64 {
         -500..=499 => {
             // Between years -500 and +500, we use the data from Table 1, except that for the year
             // -500 we changed the value 17190 to 17203.7 in order to avoid a discontinuity with the
@@ -100,24 +140,112 @@ pub fn delta_t(date: &Date) -> f64 {
                 62.92 + 0.32217 * t + 0.005_589 * (t * t)
             }
         }
-        2050..=2099 => {
+        2050..=2149 => {
+            // The last term removes the discontinuity with the parabola at 2150.
             let t = (decimal_year - 1820_f64) / 100_f64;
             -20_f64 + 32_f64 * (t * t) - 0.5628 * (2150_f64 - decimal_year)
         }
-        2150..=2999 => {
+        _ => {
+            // Before the year -500 and after 2150, the long-term parabola:
             let u = (decimal_year - 1820_f64) / 100_f64;
             -20_f64 + 32_f64 * (u * u)
         }
-        _ => panic!("Not supported"),
-    };
+    }
+}
+
+/// Table of observed (or predicted) values of ΔT, such as the ones published
+/// by the IERS and the USNO, to be used for recent dates instead of the
+/// polynomial expressions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeltaTTable {
+    /// (decimal year, ΔT in seconds), sorted by year.
+    entries: Vec<(f64, f64)>,
+}
+
+impl DeltaTTable {
+    /// Creates a table from (decimal year, ΔT in seconds) pairs.
+    #[must_use]
+    pub fn new(mut entries: Vec<(f64, f64)>) -> Self {
+        entries.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { entries }
+    }
+
+    /// Parses a table with one value per line, either `year month day ΔT` as in
+    /// the USNO `deltat.data` file or `decimal_year ΔT`. Empty lines and lines
+    /// starting with `#` are skipped.
+    ///
+    /// Returns [`Error::InvalidDeltaTTable`] with the 1-based number of the
+    /// first line that cannot be read.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let mut entries = Vec::new();
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = || Error::InvalidDeltaTTable(index + 1);
+            let fields: Vec<f64> = line
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|_| error())?;
+
+            let entry = match fields[..] {
+                [year, value] => (year, value),
+                [year, month, day, value] => {
+                    let date = Date::new(year as i16, month as u8, day);
+                    (try_get_decimal_year(&date).map_err(|_| error())?, value)
+                }
+                _ => return Err(error()),
+            };
+            entries.push(entry);
+        }
+
+        Ok(Self::new(entries))
+    }
+
+    /// Reads a table from a local file, see [`DeltaTTable::parse`] for the format.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path).map_err(|e| Error::Io(e.kind()))?;
+        Self::parse(&content)
+    }
+
+    /// Returns ΔT in seconds linearly interpolated at a decimal year, or `None`
+    /// if the year is outside the table.
+    #[must_use]
+    pub fn delta_t_for_year(&self, decimal_year: f64) -> Option<f64> {
+        let after = self
+            .entries
+            .partition_point(|(year, _)| *year < decimal_year);
+        let (year1, value1) = *self.entries.get(after)?;
+
+        if year1 == decimal_year {
+            return Some(value1);
+        }
 
-    res
+        let (year0, value0) = *self.entries.get(after.checked_sub(1)?)?;
+        Some(value0 + (value1 - value0) * (decimal_year - year0) / (year1 - year0))
+    }
+
+    /// Returns ΔT in seconds for a date from the table, falling back to
+    /// [`try_delta_t`] outside of it.
+    pub fn try_delta_t(&self, date: &Date) -> Result<f64, Error> {
+        let decimal_year = try_get_decimal_year(date)?;
+
+        match self.delta_t_for_year(decimal_year) {
+            Some(value) => Ok(value),
+            None => try_delta_t(date),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::date::Date;
-    use crate::dynamical_time::delta_t;
+    use crate::date::{Calendar, Date};
+    use crate::dynamical_time::*;
+    use crate::Error;
 
     #[test]
     fn test_delta_t() {
@@ -134,4 +262,99 @@ mod test {
         let dt = delta_t(&date);
         assert_eq!(73, math::round::half_up(dt, 0) as i64);
     }
+
+    #[test]
+    fn test_long_term_parabola() {
+        // Outside -500..2150 ΔT = -20 + 32u², u = (y - 1820) / 100
+        let date = Date::new(3500, 1, 1.0);
+        assert!((delta_t(&date) - (-20.0 + 32.0 * 16.8 * 16.8)).abs() < 1e-9);
+        assert!((delta_t_for_year(-3000.0) - 74_323.68).abs() < 1e-6);
+        assert!((delta_t_for_year(-500.0) - delta_t_for_year(-500.000_001)).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_2100_to_2150() {
+        let dt = delta_t(&Date::new(2120, 6, 1.0));
+        assert!(dt > 200.0 && dt < 300.0, "{dt}");
+        assert!((delta_t_for_year(2_149.999_999) - delta_t_for_year(2_150.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_day_resolution() {
+        assert_eq!(get_decimal_year(&Date::new(2000, 1, 1.0)), 2000.0);
+        let july = get_decimal_year(&Date::from_full_date(2001, 7, 2.0, 12, 0, 0.0));
+        assert!((july - 2001.5).abs() < 0.001);
+
+        let first = delta_t(&Date::new(2022, 1, 1.0));
+        let last = delta_t(&Date::new(2022, 1, 31.0));
+        assert!(last > first);
+
+        // the year starts on January 1st of the calendar of the date
+        let julian = Date::new(2000, 1, 1.0).with_calendar(Calendar::Julian);
+        assert_eq!(get_decimal_year(&julian), 2000.0);
+    }
+
+    #[test]
+    fn test_invalid_date_returns_error() {
+        assert_eq!(
+            try_delta_t(&Date::new(2000, 13, 1.0)),
+            Err(Error::InvalidMonth(13))
+        );
+        assert!(try_delta_t(&Date::new(2000, 1, f64::NAN)).is_err());
+    }
+
+    const TABLE: &str = "# USNO deltat.data excerpt
+2016  1  1  68.1024
+2016  2  1  68.1577
+
+2016  3  1  68.1905
+";
+
+    #[test]
+    fn test_delta_t_table() {
+        let table = DeltaTTable::parse(TABLE).unwrap();
+        let feb = get_decimal_year(&Date::new(2016, 2, 1.0));
+
+        assert_eq!(table.delta_t_for_year(feb), Some(68.1577));
+        let mid = table
+            .delta_t_for_year(get_decimal_year(&Date::new(2016, 1, 16.5)))
+            .unwrap();
+        assert!((mid - (68.1024 + 68.1577) / 2.0).abs() < 1e-9);
+        assert_eq!(table.delta_t_for_year(2015.0), None);
+        assert_eq!(table.delta_t_for_year(2017.0), None);
+
+        let date = Date::new(2016, 3, 1.0);
+        assert_eq!(table.try_delta_t(&date), Ok(68.1905));
+        let date = Date::new(2020, 1, 1.0);
+        assert_eq!(table.try_delta_t(&date), try_delta_t(&date));
+
+        let decimal = DeltaTTable::parse("2016.0 68.10\n2017.0 68.59").unwrap();
+        assert!((decimal.delta_t_for_year(2016.5).unwrap() - 68.345).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_delta_t_table_errors() {
+        assert_eq!(
+            DeltaTTable::parse("2016 1 1 68.1\n2016 2 x 68.2"),
+            Err(Error::InvalidDeltaTTable(2))
+        );
+        assert_eq!(
+            DeltaTTable::parse("2016 1 68.1"),
+            Err(Error::InvalidDeltaTTable(1))
+        );
+        assert!(matches!(
+            DeltaTTable::from_file("/nonexistent/deltat.data"),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_delta_t_table_from_file() {
+        let path = std::env::temp_dir().join("runiverse_deltat.data");
+        std::fs::write(&path, TABLE).unwrap();
+        let table = DeltaTTable::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(table, DeltaTTable::parse(TABLE).unwrap());
+    }
 }
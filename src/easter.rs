@@ -0,0 +1,190 @@
+//! Date of Easter and of the movable feasts depending on it (Meeus ch. 8).
+
+use crate::date::{Calendar, Date};
+use crate::duration::Duration;
+
+/// Feasts of the liturgical year at a fixed number of days from Easter Sunday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovableFeast {
+    /// Ash Wednesday, 46 days before Easter.
+    AshWednesday,
+    /// Palm Sunday, a week before Easter.
+    PalmSunday,
+    /// Good Friday, 2 days before Easter.
+    GoodFriday,
+    Easter,
+    /// Ascension Thursday, 39 days after Easter.
+    Ascension,
+    /// Pentecost, 49 days after Easter.
+    Pentecost,
+    /// Trinity Sunday, a week after Pentecost.
+    TrinitySunday,
+    /// Corpus Christi, the Thursday after Trinity Sunday.
+    CorpusChristi,
+}
+
+impl MovableFeast {
+    /// Number of days from Easter Sunday to the feast.
+    #[must_use]
+    pub fn days_from_easter(self) -> i16 {
+        match self {
+            Self::AshWednesday => -46,
+            Self::PalmSunday => -7,
+            Self::GoodFriday => -2,
+            Self::Easter => 0,
+            Self::Ascension => 39,
+            Self::Pentecost => 49,
+            Self::TrinitySunday => 56,
+            Self::CorpusChristi => 60,
+        }
+    }
+}
+
+/// Returns the date of Easter Sunday of the Western churches.
+///
+/// From 1583 the Gregorian computus is used (Meeus p. 67), before it the
+/// Julian computus (Meeus p. 69) with the date in the Julian calendar, as
+/// given by the default [`Calendar::Gregorian`].
+#[must_use]
+pub fn get_easter(year: i16) -> Date {
+    let (month, day) = if year > 1582 {
+        gregorian_computus(year)
+    } else {
+        julian_computus(year)
+    };
+    Date::new(year, month, f64::from(day))
+}
+
+/// Returns the date of Easter Sunday of the Orthodox churches, computed with
+/// the Julian computus (Meeus p. 69).
+///
+/// The date is given in the default [`Calendar::Gregorian`], so from 1583 it
+/// is converted to the Gregorian calendar, e.g. April 22 (Julian) is May 5 in 2024.
+#[must_use]
+pub fn get_orthodox_easter(year: i16) -> Date {
+    let (month, day) = julian_computus(year);
+    Date::new(year, month, f64::from(day))
+        .with_calendar(Calendar::Julian)
+        .to_calendar(Calendar::default())
+}
+
+/// Returns the date of a movable feast of the Western churches, see [`get_easter`].
+#[must_use]
+pub fn get_movable_feast(feast: MovableFeast, year: i16) -> Date {
+    from_easter(get_easter(year), feast)
+}
+
+/// Returns the date of a movable feast of the Orthodox churches, see [`get_orthodox_easter`].
+#[must_use]
+pub fn get_orthodox_movable_feast(feast: MovableFeast, year: i16) -> Date {
+    from_easter(get_orthodox_easter(year), feast)
+}
+
+fn from_easter(easter: Date, feast: MovableFeast) -> Date {
+    easter + Duration::from_days(f64::from(feast.days_from_easter()))
+}
+
+/// Month and day of Easter in the Gregorian calendar, valid from 1583.
+fn gregorian_computus(year: i16) -> (u8, u8) {
+    let year = i32::from(year);
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let n = (h + l - 7 * m + 114) / 31;
+    let p = (h + l - 7 * m + 114) % 31;
+
+    (n as u8, (p + 1) as u8)
+}
+
+/// Month and day of Easter in the Julian calendar.
+fn julian_computus(year: i16) -> (u8, u8) {
+    let year = i32::from(year);
+    let a = year.rem_euclid(4);
+    let b = year.rem_euclid(7);
+    let c = year.rem_euclid(19);
+    let d = (19 * c + 15) % 30;
+    let e = (2 * a + 4 * b - d + 34) % 7;
+    let f = (d + e + 114) / 31;
+    let g = (d + e + 114) % 31;
+
+    (f as u8, (g + 1) as u8)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gregorian_easter_meeus() {
+        // From Meeus book "astronomical algorithms" p. 68
+        for (year, month, day) in [
+            (1991, 3, 31.0),
+            (1992, 4, 19.0),
+            (1993, 4, 11.0),
+            (1954, 4, 18.0),
+            (2000, 4, 23.0),
+            (1818, 3, 22.0),
+        ] {
+            assert_eq!(get_easter(year), Date::new(year, month, day), "{year}");
+        }
+        // Easter is always a Sunday, in the range March 22 - April 25
+        for year in 1583..2500 {
+            let easter = get_easter(year);
+            assert_eq!((easter.to_julian_day().get_value() + 1.5) % 7.0, 0.0);
+            assert!((81..=115).contains(&(easter.year_day() - u16::from(easter.is_leap()))));
+        }
+    }
+
+    #[test]
+    fn test_julian_easter_meeus() {
+        // From Meeus book "astronomical algorithms" p. 69, the Julian dates repeat
+        // after 532 years
+        for (year, month, day) in [(179, 4, 12.0), (711, 4, 12.0), (1243, 4, 12.0)] {
+            assert_eq!(get_easter(year), Date::new(year, month, day), "{year}");
+            assert_eq!(get_orthodox_easter(year), get_easter(year));
+        }
+    }
+
+    #[test]
+    fn test_orthodox_easter() {
+        assert_eq!(get_orthodox_easter(2024), Date::new(2024, 5, 5.0));
+        assert_eq!(get_orthodox_easter(2025), get_easter(2025));
+        assert_eq!(
+            get_orthodox_easter(2024).to_calendar(Calendar::Julian),
+            Date::new(2024, 4, 22.0).with_calendar(Calendar::Julian)
+        );
+        assert_eq!(
+            get_orthodox_movable_feast(MovableFeast::Pentecost, 2024),
+            Date::new(2024, 6, 23.0)
+        );
+    }
+
+    #[test]
+    fn test_movable_feasts() {
+        for (feast, month, day) in [
+            (MovableFeast::AshWednesday, 2, 14.0),
+            (MovableFeast::PalmSunday, 3, 24.0),
+            (MovableFeast::GoodFriday, 3, 29.0),
+            (MovableFeast::Easter, 3, 31.0),
+            (MovableFeast::Ascension, 5, 9.0),
+            (MovableFeast::Pentecost, 5, 19.0),
+            (MovableFeast::TrinitySunday, 5, 26.0),
+            (MovableFeast::CorpusChristi, 5, 30.0),
+        ] {
+            assert_eq!(
+                get_movable_feast(feast, 2024),
+                Date::new(2024, month, day),
+                "{feast:?}"
+            );
+        }
+    }
+}
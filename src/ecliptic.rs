@@ -0,0 +1,156 @@
+//! Ecliptic coordinates — Meeus, *Astronomical Algorithms*, 2nd ed., Chapter 13.
+//!
+//! The conversions take the obliquity of the ecliptic explicitly so that either
+//! the mean (ε0) or the true (ε0 + Δε) obliquity from [`crate::nutation`] can be
+//! used, depending on whether mean or apparent coordinates are wanted.
+
+use crate::julian_day::JulianDay;
+use crate::nutation::{get_mean_obliquity, get_true_obliquity};
+use crate::{fit_degrees, Epoch, EqPosition};
+
+/// Position in the ecliptic coordinate system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EclipticPosition {
+    /// Ecliptic longitude λ in degrees, in the range 0-360.
+    pub longitude: f64,
+    /// Ecliptic latitude β in degrees.
+    pub latitude: f64,
+}
+
+impl EclipticPosition {
+    /// Creates a position from longitude and latitude in degrees, the longitude
+    /// is reduced to the range 0-360.
+    #[must_use]
+    pub fn new(longitude: f64, latitude: f64) -> Self {
+        Self {
+            longitude: fit_degrees(longitude),
+            latitude,
+        }
+    }
+}
+
+/// Which obliquity of the ecliptic to use for a conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Obliquity {
+    /// Mean obliquity ε0, for mean coordinates.
+    Mean,
+    /// True obliquity ε0 + Δε, for apparent coordinates.
+    True,
+}
+
+impl Obliquity {
+    /// Value of the obliquity in degrees for the given Julian Ephemeris Day.
+    #[must_use]
+    pub fn at(self, jd: &JulianDay) -> f64 {
+        match self {
+            Self::Mean => get_mean_obliquity(jd),
+            Self::True => get_true_obliquity(jd),
+        }
+    }
+}
+
+/// Converts ecliptic coordinates to equatorial ones given the obliquity of the
+/// ecliptic in degrees (Meeus eqs. 13.3 and 13.4).
+///
+/// The returned position is tagged [`Epoch::J2000`], see [`get_equ_from_ecl`]
+/// for a conversion at a given date.
+#[must_use]
+pub fn ecl_to_equ(ecl: &EclipticPosition, obliquity: f64) -> EqPosition {
+    let epsilon = obliquity.to_radians();
+    let lambda = ecl.longitude.to_radians();
+    let beta = ecl.latitude.to_radians();
+
+    let ra = (lambda.sin() * epsilon.cos() - beta.tan() * epsilon.sin()).atan2(lambda.cos());
+    let dec = (beta.sin() * epsilon.cos() + beta.cos() * epsilon.sin() * lambda.sin()).asin();
+
+    EqPosition::from_radians(ra, dec)
+}
+
+/// Converts equatorial coordinates to ecliptic ones given the obliquity of the
+/// ecliptic in degrees (Meeus eqs. 13.1 and 13.2).
+#[must_use]
+pub fn equ_to_ecl(pos: &EqPosition, obliquity: f64) -> EclipticPosition {
+    let epsilon = obliquity.to_radians();
+    let alpha = pos.ra_radians();
+    let delta = pos.dec_radians();
+
+    let lambda = (alpha.sin() * epsilon.cos() + delta.tan() * epsilon.sin()).atan2(alpha.cos());
+    let beta = (delta.sin() * epsilon.cos() - delta.cos() * epsilon.sin() * alpha.sin()).asin();
+
+    EclipticPosition::new(lambda.to_degrees(), beta.to_degrees())
+}
+
+/// Converts ecliptic coordinates of date to an equatorial position referred to
+/// the equinox of the given Julian Ephemeris Day.
+#[must_use]
+pub fn get_equ_from_ecl(
+    ecl: &EclipticPosition,
+    jd: &JulianDay,
+    obliquity: Obliquity,
+) -> EqPosition {
+    ecl_to_equ(ecl, obliquity.at(jd)).with_epoch(Epoch::OfDate(*jd))
+}
+
+/// Converts equatorial coordinates of date to ecliptic ones for the given Julian Ephemeris Day.
+#[must_use]
+pub fn get_ecl_from_equ(
+    pos: &EqPosition,
+    jd: &JulianDay,
+    obliquity: Obliquity,
+) -> EclipticPosition {
+    equ_to_ecl(pos, obliquity.at(jd))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Declination, RightAscension};
+
+    #[test]
+    fn test_equ_to_ecl_meeus_example_13a() {
+        // Meeus, Astronomical Algorithms, 2nd ed., Example 13.a, p. 95
+        // Pollux: α = 7h45m18.946s, δ = +28°01'34.26", ε = 23.4392911°
+        // Expected: λ = 113.215630°, β = 6.684170°
+        let pollux = EqPosition::new(
+            RightAscension::new(7, 45, 18.946),
+            Declination::new(28, 1, 34.26),
+        );
+        let ecl = equ_to_ecl(&pollux, 23.439_291_1);
+
+        assert!(
+            (ecl.longitude - 113.215_630).abs() < 1e-6,
+            "longitude: {}",
+            ecl.longitude
+        );
+        assert!(
+            (ecl.latitude - 6.684_170).abs() < 1e-6,
+            "latitude: {}",
+            ecl.latitude
+        );
+    }
+
+    #[test]
+    fn test_ecl_to_equ_meeus_example_13a() {
+        let ecl = EclipticPosition::new(113.215_630, 6.684_170);
+        let pos = ecl_to_equ(&ecl, 23.439_291_1);
+
+        assert_eq!(pos.ra(), RightAscension::new(7, 45, 18.946));
+        assert_eq!(pos.dec(), Declination::new(28, 1, 34.26));
+    }
+
+    #[test]
+    fn test_mean_and_true_obliquity_conversions() {
+        let jd = JulianDay::new(2_446_895.5);
+        let ecl = EclipticPosition::new(-10.0, 5.0);
+        assert_eq!(ecl.longitude, 350.0);
+
+        let mean = get_equ_from_ecl(&ecl, &jd, Obliquity::Mean);
+        let apparent = get_equ_from_ecl(&ecl, &jd, Obliquity::True);
+        assert_eq!(mean.epoch(), Epoch::OfDate(jd));
+        assert!((mean.dec_degrees() - apparent.dec_degrees()).abs() > 1e-4);
+
+        let back = get_ecl_from_equ(&apparent, &jd, Obliquity::True);
+        assert!((back.longitude - 350.0).abs() < 1e-9);
+        assert!((back.latitude - 5.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,499 @@
+//! Tables of positions at evenly spaced instants.
+//!
+//! An [`InstantRange`] yields the Julian Days between two instants, either by
+//! step or by count, and [`InstantRange::rows`] maps any function of a Julian
+//! Day over it. The rows can be collected into an [`Ephemeris`] and written as
+//! CSV for the types implementing [`Columns`].
+//!
+//! ```
+//! use runiverse::date::Date;
+//! use runiverse::duration::Duration;
+//! use runiverse::ephemeris::{Ephemeris, InstantRange};
+//! use runiverse::sun::get_sun_equ_coords;
+//!
+//! let start = Date::new(2024, 3, 18.0).to_julian_day();
+//! let end = Date::new(2024, 3, 22.0).to_julian_day();
+//! let nights = InstantRange::by_step(start, end, Duration::from_days(1.0)).inclusive();
+//!
+//! let table: Ephemeris<_> = nights.rows(get_sun_equ_coords).collect();
+//! assert_eq!(table.rows().len(), 5);
+//! assert!(table.to_csv().starts_with("jd,date,ra,dec\n2460387.50000000,2024-03-18T00:00:00.000,"));
+//! ```
+
+use std::io::Write;
+
+use crate::date::Date;
+use crate::duration::Duration;
+use crate::ecliptic::EclipticPosition;
+use crate::galactic::GalacticPosition;
+use crate::horizontal::HorizontalPosition;
+use crate::julian_day::JulianDay;
+use crate::moon::MoonPosition;
+use crate::sidereal_time::SiderealTime;
+use crate::sun::SunPosition;
+use crate::{EqPosition, Error};
+
+/// Tolerance, in steps, for an end falling on a step despite rounding errors.
+const STEP_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Spacing {
+    Step(Duration),
+    Count(usize),
+}
+
+/// Iterator over evenly spaced Julian Days from a start to an end, which is
+/// excluded unless [`InstantRange::inclusive`] is used.
+///
+/// Every instant is computed from the start, so no rounding error accumulates
+/// along the range. Ranges run backwards when the end is before the start and
+/// the step is negative.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InstantRange {
+    start: JulianDay,
+    end: JulianDay,
+    spacing: Spacing,
+    inclusive: bool,
+    front: usize,
+    back: usize,
+}
+
+impl InstantRange {
+    /// Instants from `start` every `step` up to `end`, the range is empty if
+    /// the step is zero or goes away from the end.
+    #[must_use]
+    pub fn by_step(start: JulianDay, end: JulianDay, step: Duration) -> Self {
+        Self::new(start, end, Spacing::Step(step))
+    }
+
+    /// `count` instants evenly spaced from `start` to `end`; with the end
+    /// included they are `count - 1` intervals apart, otherwise `count`.
+    #[must_use]
+    pub fn by_count(start: JulianDay, end: JulianDay, count: usize) -> Self {
+        Self::new(start, end, Spacing::Count(count))
+    }
+
+    fn new(start: JulianDay, end: JulianDay, spacing: Spacing) -> Self {
+        Self {
+            start,
+            end,
+            spacing,
+            inclusive: false,
+            front: 0,
+            back: 0,
+        }
+    }
+
+    /// Returns the same range including the end, if it falls on a step.
+    #[must_use]
+    pub fn inclusive(self) -> Self {
+        Self {
+            inclusive: true,
+            ..self
+        }
+    }
+
+    /// Interval between two consecutive instants.
+    #[must_use]
+    pub fn step(&self) -> Duration {
+        let span = self.end - self.start;
+        match self.spacing {
+            Spacing::Step(step) => step,
+            Spacing::Count(count) if self.inclusive => span / count.saturating_sub(1).max(1) as f64,
+            Spacing::Count(count) => span / count.max(1) as f64,
+        }
+    }
+
+    /// Number of instants of the whole range, including those already consumed.
+    fn total(&self) -> usize {
+        match self.spacing {
+            Spacing::Count(count) => count,
+            Spacing::Step(step) => {
+                let steps = (self.end - self.start).seconds() / step.seconds();
+                if !steps.is_finite() || steps < -STEP_TOLERANCE {
+                    0
+                } else if self.inclusive {
+                    ((steps + STEP_TOLERANCE).floor() as usize).saturating_add(1)
+                } else {
+                    (steps - STEP_TOLERANCE).ceil().max(0_f64) as usize
+                }
+            }
+        }
+    }
+
+    fn at(&self, index: usize) -> JulianDay {
+        self.start + self.step() * index as f64
+    }
+
+    /// Maps a function over the instants, e.g. a position function, yielding
+    /// the rows of an [`Ephemeris`].
+    pub fn rows<T, F>(self, mut f: F) -> impl Iterator<Item = EphemerisRow<T>>
+    where
+        F: FnMut(&JulianDay) -> T,
+    {
+        self.map(move |jd| EphemerisRow { value: f(&jd), jd })
+    }
+}
+
+impl Iterator for InstantRange {
+    type Item = JulianDay;
+
+    fn next(&mut self) -> Option<JulianDay> {
+        if self.len() == 0 {
+            return None;
+        }
+        self.front += 1;
+        Some(self.at(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for InstantRange {
+    fn len(&self) -> usize {
+        self.total().saturating_sub(self.front + self.back)
+    }
+}
+
+impl DoubleEndedIterator for InstantRange {
+    fn next_back(&mut self) -> Option<JulianDay> {
+        if self.len() == 0 {
+            return None;
+        }
+        self.back += 1;
+        Some(self.at(self.total() - self.back))
+    }
+}
+
+/// A value computed at an instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EphemerisRow<T> {
+    pub jd: JulianDay,
+    pub value: T,
+}
+
+/// A table of values computed at a sequence of instants.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ephemeris<T> {
+    rows: Vec<EphemerisRow<T>>,
+}
+
+impl<T> Ephemeris<T> {
+    /// Computes `f` at every instant of the range.
+    #[must_use]
+    pub fn compute<F>(range: InstantRange, f: F) -> Self
+    where
+        F: FnMut(&JulianDay) -> T,
+    {
+        range.rows(f).collect()
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> &[EphemerisRow<T>] {
+        &self.rows
+    }
+}
+
+impl<T: Columns> Ephemeris<T> {
+    /// Writes the table as CSV: a header line, then one line per row with the
+    /// Julian Day, its date (UTC if the Julian Day is) and the columns of the value.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        let io_error = |e: std::io::Error| Error::Io(e.kind());
+        writeln!(writer, "jd,date,{}", T::column_names().join(",")).map_err(io_error)?;
+
+        for row in &self.rows {
+            writeln!(
+                writer,
+                "{:.8},{},{}",
+                row.jd.get_value(),
+                Date::try_from_julian_day(&row.jd)?.to_fits_string(3),
+                row.value.column_values().join(",")
+            )
+            .map_err(io_error)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the table as a CSV string, see [`Ephemeris::write_csv`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the year of a Julian Day doesn't fit an `i16`.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = Vec::new();
+        self.write_csv(&mut csv).expect("Year out of range");
+        String::from_utf8(csv).expect("CSV is valid UTF-8")
+    }
+}
+
+impl<T> FromIterator<EphemerisRow<T>> for Ephemeris<T> {
+    fn from_iter<I: IntoIterator<Item = EphemerisRow<T>>>(iter: I) -> Self {
+        Self {
+            rows: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> IntoIterator for Ephemeris<T> {
+    type Item = EphemerisRow<T>;
+    type IntoIter = std::vec::IntoIter<EphemerisRow<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.into_iter()
+    }
+}
+
+/// A value written as one or more columns of an [`Ephemeris`] table, angles
+/// are written in decimal degrees.
+pub trait Columns {
+    /// Names of the columns, used in the CSV header.
+    fn column_names() -> Vec<String>;
+
+    /// Values of the columns, in the order of [`Columns::column_names`].
+    fn column_values(&self) -> Vec<String>;
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| (*name).to_owned()).collect()
+}
+
+fn degrees(value: f64) -> String {
+    format!("{value:.9}")
+}
+
+impl Columns for f64 {
+    fn column_names() -> Vec<String> {
+        names(&["value"])
+    }
+
+    fn column_values(&self) -> Vec<String> {
+        vec![self.to_string()]
+    }
+}
+
+/// The columns of both values, named after their index in the pair (e.g.
+/// `0.ra`, `1.ra`) so the CSV header has no duplicate names.
+impl<A: Columns, B: Columns> Columns for (A, B) {
+    fn column_names() -> Vec<String> {
+        let first = A::column_names()
+            .into_iter()
+            .map(|name| format!("0.{name}"));
+        let second = B::column_names()
+            .into_iter()
+            .map(|name| format!("1.{name}"));
+        first.chain(second).collect()
+    }
+
+    fn column_values(&self) -> Vec<String> {
+        [self.0.column_values(), self.1.column_values()].concat()
+    }
+}
+
+impl Columns for EqPosition {
+    fn column_names() -> Vec<String> {
+        names(&["ra", "dec"])
+    }
+
+    fn column_values(&self) -> Vec<String> {
+        vec![degrees(self.ra_degrees()), degrees(self.dec_degrees())]
+    }
+}
+
+impl Columns for HorizontalPosition {
+    fn column_names() -> Vec<String> {
+        names(&["azimuth", "altitude"])
+    }
+
+    fn column_values(&self) -> Vec<String> {
+        vec![degrees(self.azimuth), degrees(self.altitude)]
+    }
+}
+
+impl Columns for EclipticPosition {
+    fn column_names() -> Vec<String> {
+        names(&["longitude", "latitude"])
+    }
+
+    fn column_values(&self) -> Vec<String> {
+        vec![degrees(self.longitude), degrees(self.latitude)]
+    }
+}
+
+impl Columns for GalacticPosition {
+    fn column_names() -> Vec<String> {
+        names(&["gal_longitude", "gal_latitude"])
+    }
+
+    fn column_values(&self) -> Vec<String> {
+        vec![degrees(self.longitude), degrees(self.latitude)]
+    }
+}
+
+impl Columns for SiderealTime {
+    fn column_names() -> Vec<String> {
+        names(&["sidereal_time"])
+    }
+
+    fn column_values(&self) -> Vec<String> {
+        vec![degrees(self.degrees)]
+    }
+}
+
+impl Columns for SunPosition {
+    fn column_names() -> Vec<String> {
+        names(&["true_longitude", "apparent_longitude", "radius_vector"])
+    }
+
+    fn column_values(&self) -> Vec<String> {
+        vec![
+            degrees(self.true_longitude),
+            degrees(self.apparent_longitude),
+            format!("{:.9}", self.radius_vector),
+        ]
+    }
+}
+
+impl Columns for MoonPosition {
+    fn column_names() -> Vec<String> {
+        names(&["longitude", "latitude", "distance", "ra", "dec"])
+    }
+
+    fn column_values(&self) -> Vec<String> {
+        vec![
+            degrees(self.longitude),
+            degrees(self.latitude),
+            format!("{:.3}", self.distance),
+            degrees(self.ra),
+            degrees(self.dec),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sun::get_sun_position;
+
+    fn j2000() -> JulianDay {
+        JulianDay::new(2_451_545.0)
+    }
+
+    #[test]
+    fn test_range_by_step() {
+        let end = j2000() + 1_f64;
+        let step = Duration::from_hours(6.0);
+
+        let range = InstantRange::by_step(j2000(), end, step);
+        assert_eq!(range.len(), 4);
+        let days: Vec<f64> = range.map(|jd| jd.days_since(&j2000())).collect();
+        assert_eq!(days, [0.0, 0.25, 0.5, 0.75]);
+
+        let mut inclusive = range.inclusive();
+        assert_eq!(inclusive.len(), 5);
+        assert_eq!(inclusive.next_back(), Some(end));
+
+        // the end isn't reached by a whole number of steps
+        let range = InstantRange::by_step(j2000(), end, Duration::from_hours(7.0));
+        assert_eq!(range.len(), 4);
+        assert_eq!(range.inclusive().len(), 4);
+    }
+
+    #[test]
+    fn test_range_by_count() {
+        let end = j2000() + 1_f64;
+        let range = InstantRange::by_count(j2000(), end, 4);
+        assert_eq!(range.step(), Duration::from_hours(6.0));
+        assert_eq!(range.len(), 4);
+
+        let mut inclusive = InstantRange::by_count(j2000(), end, 5).inclusive();
+        assert_eq!(inclusive.step(), Duration::from_hours(6.0));
+        assert_eq!(inclusive.next_back(), Some(end));
+
+        assert_eq!(
+            InstantRange::by_count(j2000(), end, 1)
+                .inclusive()
+                .collect::<Vec<_>>(),
+            [j2000()]
+        );
+        assert_eq!(InstantRange::by_count(j2000(), end, 0).count(), 0);
+    }
+
+    #[test]
+    fn test_range_directions() {
+        let start = j2000() + 1_f64;
+        let backwards =
+            InstantRange::by_step(start, j2000(), -Duration::from_hours(12.0)).inclusive();
+        let days: Vec<f64> = backwards.map(|jd| jd.days_since(&j2000())).collect();
+        assert_eq!(days, [1.0, 0.5, 0.0]);
+
+        assert_eq!(
+            InstantRange::by_step(start, j2000(), Duration::from_hours(12.0)).count(),
+            0
+        );
+        assert_eq!(
+            InstantRange::by_step(j2000(), start, Duration::ZERO).count(),
+            0
+        );
+        assert_eq!(
+            InstantRange::by_step(j2000(), j2000(), Duration::from_hours(1.0))
+                .inclusive()
+                .count(),
+            1
+        );
+
+        let mut range = InstantRange::by_step(j2000(), start, Duration::from_hours(6.0));
+        assert_eq!(range.next(), Some(j2000()));
+        assert_eq!(range.next_back(), Some(j2000() + 0.75));
+        assert_eq!(range.len(), 2);
+
+        // too many steps to count saturate instead of overflowing
+        let range = InstantRange::by_step(
+            JulianDay::new(0.0),
+            JulianDay::new(1e15),
+            Duration::from_seconds(1e-9),
+        );
+        assert_eq!(range.inclusive().len(), usize::MAX);
+        assert_eq!(range.inclusive().next(), Some(JulianDay::new(0.0)));
+    }
+
+    #[test]
+    fn test_ephemeris_csv() {
+        // From Meeus book "astronomical algorithms" p. 165 example 25.a
+        let jde = JulianDay::new(2_448_908.5);
+        let range = InstantRange::by_step(jde, jde + 2_f64, Duration::from_days(1.0));
+        let table = Ephemeris::compute(range, get_sun_position);
+        assert_eq!(table.rows().len(), 2);
+        assert!((table.rows()[0].value.apparent_longitude - 199.908_95).abs() < 1e-4);
+
+        let csv = table.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "jd,date,true_longitude,apparent_longitude,radius_vector"
+        );
+        assert!(lines[1].starts_with("2448908.50000000,1992-10-13T00:00:00.000,199.90"));
+        assert!(lines[2].starts_with("2448909.50000000,1992-10-14T00:00:00.000,"));
+
+        let pairs: Ephemeris<(f64, f64)> = InstantRange::by_count(jde, jde + 1_f64, 2)
+            .rows(|jd| (jd.fraction(), jd.days_since(&jde)))
+            .collect();
+        assert_eq!(
+            pairs.to_csv(),
+            "jd,date,0.value,1.value\n2448908.50000000,1992-10-13T00:00:00.000,0.5,0\n\
+             2448909.00000000,1992-10-13T12:00:00.000,0,0.5\n"
+        );
+
+        let far: Ephemeris<f64> =
+            InstantRange::by_count(JulianDay::new(1e15), JulianDay::new(1e15), 1)
+                .rows(|_| 0.0)
+                .collect();
+        assert!(matches!(
+            far.write_csv(Vec::new()),
+            Err(Error::YearOutOfRange(_))
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+//! Errors returned by the fallible (`try_*` and `FromStr`) entry points of the crate.
+
+use std::fmt::{self, Display, Formatter};
+
+/// Crate-wide error type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The string could not be parsed as a right ascension.
+    InvalidRightAscension(ParseError),
+    /// The string could not be parsed as a degrees/minutes/seconds angle.
+    InvalidDegMinSec(ParseError),
+    /// The string could not be parsed as a date.
+    InvalidDate(ParseError),
+    /// The string could not be parsed as an epoch.
+    InvalidEpoch(ParseError),
+    /// The string could not be split into a right ascension and a declination.
+    InvalidPosition(ParseError),
+    /// The month is outside the range 1-12.
+    InvalidMonth(u8),
+    /// The day does not exist in the month of the calendar, e.g. February 30
+    /// or a day skipped at the Gregorian switchover.
+    InvalidDay(f64),
+    /// The day of the year is 0 or beyond the end of the year.
+    InvalidDayOfYear(u16),
+    /// The year of a calendar is outside the range of years of a [`Date`](crate::date::Date).
+    YearOutOfRange(i32),
+    /// ΔT is not available for the given decimal year.
+    DeltaTOutOfRange(f64),
+    /// The line (1-based) of a ΔT table could not be read.
+    InvalidDeltaTTable(usize),
+    /// A file could not be read or written.
+    Io(std::io::ErrorKind),
+    /// A time zone file or rule is malformed, with the reason.
+    InvalidTimeZone(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidRightAscension(e) => write!(f, "cannot parse RA string {e}"),
+            Self::InvalidDegMinSec(e) => write!(f, "cannot parse DMS string {e}"),
+            Self::InvalidDate(e) => write!(f, "cannot parse date string {e}"),
+            Self::InvalidEpoch(e) => write!(f, "cannot parse epoch string {e}"),
+            Self::InvalidPosition(e) => write!(f, "cannot parse position string {e}"),
+            Self::InvalidMonth(m) => write!(f, "month {m} is outside the range 1-12"),
+            Self::InvalidDay(d) => write!(f, "day {d} does not exist in the month"),
+            Self::InvalidDayOfYear(d) => write!(f, "day {d} does not exist in the year"),
+            Self::YearOutOfRange(y) => write!(f, "year {y} is outside the supported range"),
+            Self::DeltaTOutOfRange(y) => write!(f, "delta T is not supported for year {y}"),
+            Self::InvalidDeltaTTable(line) => write!(f, "invalid delta T table at line {line}"),
+            Self::Io(kind) => write!(f, "I/O error: {kind}"),
+            Self::InvalidTimeZone(reason) => write!(f, "invalid time zone: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// What went wrong while parsing a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The string is empty or contains only whitespace.
+    Empty,
+    /// The string ended where more input was expected.
+    UnexpectedEnd,
+    /// A character that is not valid at this position was found.
+    UnexpectedCharacter(char),
+    /// A number could not be read.
+    InvalidNumber,
+    /// A field is outside its allowed range (e.g. 60 minutes or 24 hours).
+    OutOfRange,
+    /// A right ascension was given a negative sign.
+    NegativeRightAscension,
+}
+
+/// Error returned when a string cannot be parsed, recording where parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The string that was being parsed.
+    pub input: String,
+    /// Byte offset in `input` where parsing failed.
+    pub position: usize,
+    /// Reason of the failure.
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub(crate) fn new(input: &str, position: usize, kind: ParseErrorKind) -> Self {
+        Self {
+            input: input.to_string(),
+            position,
+            kind,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "\"{}\": ", self.input)?;
+        match self.kind {
+            ParseErrorKind::Empty => write!(f, "empty string"),
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseErrorKind::UnexpectedCharacter(c) => {
+                write!(
+                    f,
+                    "unexpected character '{c}' at position {}",
+                    self.position
+                )
+            }
+            ParseErrorKind::InvalidNumber => {
+                write!(f, "invalid number at position {}", self.position)
+            }
+            ParseErrorKind::OutOfRange => {
+                write!(f, "value out of range at position {}", self.position)
+            }
+            ParseErrorKind::NegativeRightAscension => {
+                write!(f, "right ascension cannot be negative")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,341 @@
+//! Configurable formatting of [`HoursMinSec`] and [`DegMinSec`] values.
+//!
+//! The `Display` impls of those types produce a fixed human readable form; a
+//! [`SexagesimalFormat`] lets callers produce the strings expected by mount
+//! controllers (LX200), FITS headers or reports from the same values.
+
+use crate::{DegMinSec, HoursMinSec};
+
+/// Separators placed after the hours/degrees, minutes and seconds fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Separator {
+    /// `12:34:56.78`, `+41:16:09.00`
+    Colon,
+    /// `12 34 56.78`, `+41 16 09.00`
+    Space,
+    /// `12h34m56.78s`, `+41d16m09.00s`
+    Letters,
+    /// `12ʰ34ᵐ56.78ˢ`, `+41°16′09.00″`
+    Unicode,
+    /// Any three separators, e.g. `("*", ":", "")` for LX200 declinations.
+    Custom(&'static str, &'static str, &'static str),
+}
+
+/// When to print the sign of an angle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignPolicy {
+    /// Print `-` for negative angles only.
+    NegativeOnly,
+    /// Always print `+` or `-`, as usually done for declinations.
+    Always,
+}
+
+/// Builder describing how a sexagesimal value is turned into a string.
+///
+/// Seconds are rounded to the requested precision and carried into minutes
+/// and hours/degrees, so 59.999s printed with 2 decimals becomes the next minute.
+///
+/// ```
+/// use runiverse::format::{SexagesimalFormat, Separator, SignPolicy};
+/// use runiverse::{Declination, RightAscension};
+///
+/// let fmt = SexagesimalFormat::new()
+///     .separator(Separator::Colon)
+///     .precision(1)
+///     .sign(SignPolicy::Always);
+///
+/// assert_eq!(fmt.format_hms(&RightAscension::new(5, 9, 59.96)), "05:10:00.0");
+/// assert_eq!(fmt.format_dms(&Declination::new(7, 4, 3.21)), "+07:04:03.2");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SexagesimalFormat {
+    separator: Separator,
+    zero_pad: bool,
+    leading_width: usize,
+    precision: usize,
+    sign: SignPolicy,
+}
+
+impl Default for SexagesimalFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SexagesimalFormat {
+    /// Largest number of decimals for the seconds, a nanosecond (of time or arc)
+    /// is well below the resolution of the `f64` seconds of a value.
+    pub const MAX_PRECISION: usize = 9;
+
+    /// Space separated, zero padded, 2 decimals for the seconds and sign
+    /// printed only for negative angles.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            separator: Separator::Space,
+            zero_pad: true,
+            leading_width: 2,
+            precision: 2,
+            sign: SignPolicy::NegativeOnly,
+        }
+    }
+
+    /// `HH:MM:SS` right ascension as used by the LX200 protocol.
+    #[must_use]
+    pub fn lx200_ra() -> Self {
+        Self::new().separator(Separator::Colon).precision(0)
+    }
+
+    /// `sDD*MM:SS` declination as used by the LX200 protocol.
+    #[must_use]
+    pub fn lx200_dec() -> Self {
+        Self::new()
+            .separator(Separator::Custom("*", ":", ""))
+            .precision(0)
+            .sign(SignPolicy::Always)
+    }
+
+    /// `HH MM SS.ss` / `+DD MM SS.ss` strings as found in `OBJCTRA`/`OBJCTDEC` FITS keywords.
+    #[must_use]
+    pub fn fits() -> Self {
+        Self::new().sign(SignPolicy::Always)
+    }
+
+    #[must_use]
+    pub fn separator(mut self, separator: Separator) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Pad minutes and seconds to 2 digits and the leading field to
+    /// [`SexagesimalFormat::leading_width`] digits.
+    #[must_use]
+    pub fn zero_pad(mut self, zero_pad: bool) -> Self {
+        self.zero_pad = zero_pad;
+        self
+    }
+
+    /// Width of the hours/degrees field when zero padding, 3 is handy for longitudes.
+    #[must_use]
+    pub fn leading_width(mut self, width: usize) -> Self {
+        self.leading_width = width;
+        self
+    }
+
+    /// Number of decimals printed for the seconds, at most
+    /// [`SexagesimalFormat::MAX_PRECISION`]; larger values are clamped.
+    #[must_use]
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision.min(Self::MAX_PRECISION);
+        self
+    }
+
+    /// Sign policy, only meaningful for [`DegMinSec`] values.
+    #[must_use]
+    pub fn sign(mut self, sign: SignPolicy) -> Self {
+        self.sign = sign;
+        self
+    }
+
+    /// Formats a right ascension, wrapping 24h back to 0h after rounding.
+    #[must_use]
+    pub fn format_hms(&self, hms: &HoursMinSec) -> String {
+        let (hours, minutes, seconds) = self.round(u32::from(hms.hours), hms.minutes, hms.seconds);
+        self.write(
+            "",
+            hours % 24,
+            minutes,
+            seconds,
+            ("h", "m", "s"),
+            ("ʰ", "ᵐ", "ˢ"),
+        )
+    }
+
+    /// Formats a degrees, minutes and seconds angle.
+    #[must_use]
+    pub fn format_dms(&self, dms: &DegMinSec) -> String {
+        let (degrees, minutes, seconds) = self.round(
+            u32::from(dms.degrees.unsigned_abs()),
+            dms.minutes,
+            dms.seconds,
+        );
+
+        let is_zero = degrees == 0 && minutes == 0 && seconds == 0;
+        let sign = match (dms.negative && !is_zero, self.sign) {
+            (true, _) => "-",
+            (false, SignPolicy::Always) => "+",
+            (false, SignPolicy::NegativeOnly) => "",
+        };
+
+        self.write(
+            sign,
+            degrees,
+            minutes,
+            seconds,
+            ("d", "m", "s"),
+            ("°", "′", "″"),
+        )
+    }
+
+    /// Rounds the seconds to the requested precision carrying into the upper fields.
+    /// Seconds are returned in units of `10^-precision`.
+    fn round(&self, whole: u32, minutes: u8, seconds: f64) -> (u32, u64, u64) {
+        let scale = 10_u64.pow(self.precision as u32);
+        let total = (u64::from(whole) * 3600 + u64::from(minutes) * 60) * scale
+            + (seconds * scale as f64).round() as u64;
+
+        let seconds = total % (60 * scale);
+        let minutes = (total / (60 * scale)) % 60;
+        let whole = total / (3600 * scale);
+
+        (whole as u32, minutes, seconds)
+    }
+
+    fn write(
+        &self,
+        sign: &str,
+        whole: u32,
+        minutes: u64,
+        seconds: u64,
+        letters: (&str, &str, &str),
+        unicode: (&str, &str, &str),
+    ) -> String {
+        let (sep_whole, sep_minutes, sep_seconds) = match self.separator {
+            Separator::Colon => (":", ":", ""),
+            Separator::Space => (" ", " ", ""),
+            Separator::Letters => letters,
+            Separator::Unicode => unicode,
+            Separator::Custom(a, b, c) => (a, b, c),
+        };
+
+        let scale = 10_u64.pow(self.precision as u32);
+        let int_secs = seconds / scale;
+        let frac_secs = if self.precision > 0 {
+            format!(".{:0width$}", seconds % scale, width = self.precision)
+        } else {
+            String::new()
+        };
+
+        let (whole_width, width) = if self.zero_pad {
+            (self.leading_width, 2)
+        } else {
+            (1, 1)
+        };
+
+        format!(
+            "{sign}{whole:0whole_width$}{sep_whole}{minutes:0width$}{sep_minutes}{int_secs:0width$}{frac_secs}{sep_seconds}"
+        )
+    }
+}
+
+impl HoursMinSec {
+    /// Formats the value with the given [`SexagesimalFormat`].
+    #[must_use]
+    pub fn format_with(&self, format: &SexagesimalFormat) -> String {
+        format.format_hms(self)
+    }
+}
+
+impl DegMinSec {
+    /// Formats the value with the given [`SexagesimalFormat`].
+    #[must_use]
+    pub fn format_with(&self, format: &SexagesimalFormat) -> String {
+        format.format_dms(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::format::{Separator, SexagesimalFormat, SignPolicy};
+    use crate::{Declination, DegMinSec, RightAscension};
+
+    #[test]
+    fn test_separators() {
+        let ra = RightAscension::new(2, 4, 6.789);
+        let fmt = SexagesimalFormat::new();
+        assert_eq!(ra.format_with(&fmt), "02 04 06.79");
+        assert_eq!(
+            ra.format_with(&fmt.separator(Separator::Colon)),
+            "02:04:06.79"
+        );
+        assert_eq!(
+            ra.format_with(&fmt.separator(Separator::Letters)),
+            "02h04m06.79s"
+        );
+        assert_eq!(
+            ra.format_with(&fmt.separator(Separator::Unicode)),
+            "02ʰ04ᵐ06.79ˢ"
+        );
+
+        let dec = Declination::new(-8, 3, 1.5);
+        assert_eq!(
+            dec.format_with(&fmt.separator(Separator::Unicode)),
+            "-08°03′01.50″"
+        );
+        assert_eq!(
+            dec.format_with(&fmt.separator(Separator::Letters).zero_pad(false)),
+            "-8d3m1.50s"
+        );
+    }
+
+    #[test]
+    fn test_rounding_carries() {
+        let fmt = SexagesimalFormat::new().precision(2);
+        let ra = RightAscension::new(23, 59, 59.999);
+        assert_eq!(ra.format_with(&fmt), "00 00 00.00");
+        assert_eq!(
+            RightAscension::new(5, 9, 59.999).format_with(&fmt),
+            "05 10 00.00"
+        );
+
+        let dec = Declination::new(41, 59, 59.9999);
+        assert_eq!(dec.format_with(&fmt.precision(3)), "42 00 00.000");
+
+        let tiny = DegMinSec {
+            negative: true,
+            degrees: 0,
+            minutes: 0,
+            seconds: 0.001,
+        };
+        assert_eq!(tiny.format_with(&fmt.precision(0)), "00 00 00");
+    }
+
+    #[test]
+    fn test_large_precision_is_clamped() {
+        let fmt = SexagesimalFormat::new().precision(15);
+        assert_eq!(fmt, SexagesimalFormat::new().precision(9));
+        assert_eq!(
+            Declination::new(-359, 59, 59.5).format_with(&fmt),
+            "-359 59 59.500000000"
+        );
+        assert_eq!(
+            RightAscension::new(23, 59, 59.9999999999).format_with(&fmt.precision(usize::MAX)),
+            "00 00 00.000000000"
+        );
+    }
+
+    #[test]
+    fn test_sign_policy_and_presets() {
+        let dec = Declination::new(7, 4, 3.21);
+        let fmt = SexagesimalFormat::new();
+        assert_eq!(dec.format_with(&fmt), "07 04 03.21");
+        assert_eq!(
+            dec.format_with(&fmt.sign(SignPolicy::Always)),
+            "+07 04 03.21"
+        );
+        assert_eq!(
+            dec.format_with(&SexagesimalFormat::lx200_dec()),
+            "+07*04:03"
+        );
+        assert_eq!(
+            RightAscension::new(12, 3, 4.6).format_with(&SexagesimalFormat::lx200_ra()),
+            "12:03:05"
+        );
+
+        let long = Declination::new(-7, 30, 0.0);
+        assert_eq!(
+            long.format_with(&fmt.leading_width(3).precision(0)),
+            "-007 30 00"
+        );
+    }
+}
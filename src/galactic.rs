@@ -0,0 +1,225 @@
+//! Galactic and supergalactic coordinates.
+//!
+//! The B1950 conversions use the IAU 1958 definition of the galactic system
+//! (north galactic pole at α = 12h49m, δ = +27°24', galactic longitude of the
+//! north celestial pole 123°) as in Meeus, *Astronomical Algorithms*, 2nd ed.,
+//! Chapter 13. The J2000 conversions use the same system expressed in FK5
+//! (pole at α = 192.85948°, δ = +27.12825°, longitude of the celestial pole
+//! 122.93192°). Supergalactic coordinates follow de Vaucouleurs et al. (1976):
+//! north supergalactic pole at l = 47.37°, b = +6.32°, origin at l = 137.37°, b = 0°.
+//!
+//! Equatorial positions referred to another equinox are first precessed with
+//! [`precess_equ`], which ignores the small FK4 to FK5 frame difference.
+
+use crate::precession::precess_equ;
+use crate::{fit_degrees, Epoch, EqPosition};
+
+/// Position in the galactic coordinate system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GalacticPosition {
+    /// Galactic longitude l in degrees, in the range 0-360.
+    pub longitude: f64,
+    /// Galactic latitude b in degrees.
+    pub latitude: f64,
+}
+
+/// Position in the supergalactic coordinate system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SupergalacticPosition {
+    /// Supergalactic longitude SGL in degrees, in the range 0-360.
+    pub longitude: f64,
+    /// Supergalactic latitude SGB in degrees.
+    pub latitude: f64,
+}
+
+// Pole of the target system in the source system (longitude, latitude) and
+// longitude of the source pole in the target system, all in degrees.
+const GAL_POLE_B1950: (f64, f64, f64) = (192.25, 27.4, 123.0);
+const GAL_POLE_J2000: (f64, f64, f64) = (192.859_48, 27.128_25, 122.931_92);
+const SUPERGAL_POLE: (f64, f64, f64) = (47.37, 6.32, 90.0);
+
+/// Rotates spherical coordinates into a system whose pole is at
+/// (`pole_lon`, `pole_lat`) and where the source pole has longitude `node`.
+fn rotate(lon: f64, lat: f64, (pole_lon, pole_lat, node): (f64, f64, f64)) -> (f64, f64) {
+    let lat = lat.to_radians();
+    let pole_lat = pole_lat.to_radians();
+    let d_lon = (lon - pole_lon).to_radians();
+
+    let x = lat.sin() * pole_lat.cos() - lat.cos() * pole_lat.sin() * d_lon.cos();
+    let y = lat.cos() * d_lon.sin();
+    let new_lat = (lat.sin() * pole_lat.sin() + lat.cos() * pole_lat.cos() * d_lon.cos()).asin();
+
+    (
+        fit_degrees(node - y.atan2(x).to_degrees()),
+        new_lat.to_degrees(),
+    )
+}
+
+/// Inverse of [`rotate`]: the roles of the pole longitude and the node swap.
+fn rotate_back(lon: f64, lat: f64, (pole_lon, pole_lat, node): (f64, f64, f64)) -> (f64, f64) {
+    rotate(lon, lat, (node, pole_lat, pole_lon))
+}
+
+fn to_galactic(pos: &EqPosition, pole: (f64, f64, f64), epoch: Epoch) -> GalacticPosition {
+    let pos = if pos.epoch() == epoch {
+        *pos
+    } else {
+        precess_equ(pos, epoch)
+    };
+    let (longitude, latitude) = rotate(pos.ra_degrees(), pos.dec_degrees(), pole);
+    GalacticPosition {
+        longitude,
+        latitude,
+    }
+}
+
+fn from_galactic(gal: &GalacticPosition, pole: (f64, f64, f64), epoch: Epoch) -> EqPosition {
+    let (ra, dec) = rotate_back(gal.longitude, gal.latitude, pole);
+    EqPosition::from_degrees(ra, dec).with_epoch(epoch)
+}
+
+/// Converts an equatorial position to galactic coordinates (Meeus eqs. 13.7
+/// and 13.8), precessing it to B1950 if it is referred to another equinox.
+#[must_use]
+pub fn get_gal_from_equ(pos: &EqPosition) -> GalacticPosition {
+    to_galactic(pos, GAL_POLE_B1950, Epoch::B1950)
+}
+
+/// Converts galactic coordinates to a B1950 equatorial position.
+#[must_use]
+pub fn get_equ_from_gal(gal: &GalacticPosition) -> EqPosition {
+    from_galactic(gal, GAL_POLE_B1950, Epoch::B1950)
+}
+
+/// Converts an equatorial position to galactic coordinates, precessing it to
+/// J2000 if it is referred to another equinox.
+#[must_use]
+pub fn get_gal_from_equ2000(pos: &EqPosition) -> GalacticPosition {
+    to_galactic(pos, GAL_POLE_J2000, Epoch::J2000)
+}
+
+/// Converts galactic coordinates to a J2000 equatorial position.
+#[must_use]
+pub fn get_equ2000_from_gal(gal: &GalacticPosition) -> EqPosition {
+    from_galactic(gal, GAL_POLE_J2000, Epoch::J2000)
+}
+
+/// Converts galactic coordinates to supergalactic ones.
+#[must_use]
+pub fn get_supergal_from_gal(gal: &GalacticPosition) -> SupergalacticPosition {
+    let (longitude, latitude) = rotate(gal.longitude, gal.latitude, SUPERGAL_POLE);
+    SupergalacticPosition {
+        longitude,
+        latitude,
+    }
+}
+
+/// Converts supergalactic coordinates to galactic ones.
+#[must_use]
+pub fn get_gal_from_supergal(sgal: &SupergalacticPosition) -> GalacticPosition {
+    let (longitude, latitude) = rotate_back(sgal.longitude, sgal.latitude, SUPERGAL_POLE);
+    GalacticPosition {
+        longitude,
+        latitude,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Declination, RightAscension};
+
+    fn assert_close(actual: (f64, f64), expected: (f64, f64), tolerance: f64) {
+        let d_lon = (actual.0 - expected.0 + 180.0).rem_euclid(360.0) - 180.0;
+        assert!(
+            d_lon.abs() < tolerance && (actual.1 - expected.1).abs() < tolerance,
+            "got {actual:?}, expected {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_gal_from_equ_meeus_example_13c() {
+        // Meeus, Astronomical Algorithms, 2nd ed., Example 13.c, p. 96
+        // Nova Serpentis 1978: α1950 = 17h48m59.74s, δ1950 = −14°43'08.2"
+        // Expected: l = 12.9593°, b = +6.0463°
+        let nova = EqPosition::new(
+            RightAscension::new(17, 48, 59.74),
+            Declination::new(-14, 43, 8.2),
+        )
+        .with_epoch(Epoch::B1950);
+        let gal = get_gal_from_equ(&nova);
+        assert_close((gal.longitude, gal.latitude), (12.9593, 6.0463), 1e-4);
+
+        let back = get_equ_from_gal(&gal);
+        assert_eq!(back.epoch(), Epoch::B1950);
+        assert_close(
+            (back.ra_degrees(), back.dec_degrees()),
+            (nova.ra_degrees(), nova.dec_degrees()),
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn test_galactic_centre_and_pole_j2000() {
+        let centre = get_equ2000_from_gal(&GalacticPosition {
+            longitude: 0.0,
+            latitude: 0.0,
+        });
+        assert_eq!(centre.epoch(), Epoch::J2000);
+        assert_close(
+            (centre.ra_degrees(), centre.dec_degrees()),
+            (266.404_99, -28.936_17),
+            1e-4,
+        );
+
+        let pole = get_gal_from_equ2000(&EqPosition::from_degrees(192.859_48, 27.128_25));
+        assert!((pole.latitude - 90.0).abs() < 1e-9);
+
+        let celestial_pole = get_gal_from_equ2000(&EqPosition::from_degrees(0.0, 90.0));
+        assert_close(
+            (celestial_pole.longitude, celestial_pole.latitude),
+            (122.931_92, 27.128_25),
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn test_gal_from_equ_precesses_other_epochs() {
+        let nova = EqPosition::new(
+            RightAscension::new(17, 48, 59.74),
+            Declination::new(-14, 43, 8.2),
+        )
+        .with_epoch(Epoch::B1950);
+        let nova_j2000 = precess_equ(&nova, Epoch::J2000);
+        assert!((nova_j2000.ra_degrees() - nova.ra_degrees()).abs() > 0.1);
+
+        let gal = get_gal_from_equ(&nova_j2000);
+        assert_close((gal.longitude, gal.latitude), (12.9593, 6.0463), 1e-4);
+
+        // the same galactic coordinates from both systems, to the FK4/FK5 difference
+        let gal = get_gal_from_equ2000(&nova);
+        assert_close((gal.longitude, gal.latitude), (12.9593, 6.0463), 1e-3);
+    }
+
+    #[test]
+    fn test_supergalactic_pole_and_origin() {
+        let pole = get_supergal_from_gal(&GalacticPosition {
+            longitude: 47.37,
+            latitude: 6.32,
+        });
+        assert!((pole.latitude - 90.0).abs() < 1e-9);
+
+        let origin = get_supergal_from_gal(&GalacticPosition {
+            longitude: 137.37,
+            latitude: 0.0,
+        });
+        assert_close((origin.longitude, origin.latitude), (0.0, 0.0), 1e-9);
+
+        let gal = GalacticPosition {
+            longitude: 283.8,
+            latitude: 74.5,
+        };
+        let back = get_gal_from_supergal(&get_supergal_from_gal(&gal));
+        assert_close((back.longitude, back.latitude), (283.8, 74.5), 1e-9);
+    }
+}
@@ -0,0 +1,180 @@
+//! Horizontal coordinates — Meeus, *Astronomical Algorithms*, 2nd ed., Chapter 13.
+//!
+//! Azimuth is measured from the North towards the East (N = 0°, E = 90°,
+//! S = 180°, W = 270°) as usually done by mounts and planetarium software,
+//! unlike Meeus and libnova which measure it westwards from the South.
+//! Observer longitudes are positive east of Greenwich.
+
+use crate::julian_day::JulianDay;
+use crate::sidereal_time::get_mean_sidereal_time;
+use crate::{fit_degrees, Epoch, EqPosition, LongLatPosition};
+
+/// Position of an object in the sky of an observer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HorizontalPosition {
+    /// Azimuth in degrees, measured from the North towards the East.
+    pub azimuth: f64,
+    /// Altitude above the horizon in degrees.
+    pub altitude: f64,
+}
+
+/// Converts an equatorial position to horizontal coordinates for an observer
+/// at the given instant (UT), using the mean sidereal time at Greenwich.
+///
+/// For the best accuracy `pos` should hold apparent coordinates of date.
+#[must_use]
+pub fn get_hrz_from_equ(
+    pos: &EqPosition,
+    observer: &LongLatPosition,
+    jd: &JulianDay,
+) -> HorizontalPosition {
+    let sidereal_time = get_mean_sidereal_time(jd).degrees;
+    get_hrz_from_equ_sidereal_time(pos, observer, sidereal_time)
+}
+
+/// Converts an equatorial position to horizontal coordinates for an observer,
+/// given the sidereal time at Greenwich in degrees (mean or apparent).
+///
+/// Algorithm: Meeus eqs. 13.5 and 13.6.
+#[must_use]
+pub fn get_hrz_from_equ_sidereal_time(
+    pos: &EqPosition,
+    observer: &LongLatPosition,
+    sidereal_time: f64,
+) -> HorizontalPosition {
+    let hour_angle = (sidereal_time + observer.long_degrees() - pos.ra_degrees()).to_radians();
+    let lat = observer.lat_degrees().to_radians();
+    let dec = pos.dec_radians();
+
+    // Azimuth measured from the South (Meeus 13.5)
+    let azimuth_south = hour_angle
+        .sin()
+        .atan2(hour_angle.cos() * lat.sin() - dec.tan() * lat.cos());
+    // Meeus 13.6
+    let altitude = (lat.sin() * dec.sin() + lat.cos() * dec.cos() * hour_angle.cos()).asin();
+
+    HorizontalPosition {
+        azimuth: fit_degrees(azimuth_south.to_degrees() + 180.0),
+        altitude: altitude.to_degrees(),
+    }
+}
+
+/// Converts horizontal coordinates of an observer at the given instant (UT) to
+/// an equatorial position referred to the equinox of date.
+#[must_use]
+pub fn get_equ_from_hrz(
+    hrz: &HorizontalPosition,
+    observer: &LongLatPosition,
+    jd: &JulianDay,
+) -> EqPosition {
+    let sidereal_time = get_mean_sidereal_time(jd).degrees;
+    get_equ_from_hrz_sidereal_time(hrz, observer, sidereal_time).with_epoch(Epoch::OfDate(*jd))
+}
+
+/// Converts horizontal coordinates of an observer to an equatorial position,
+/// given the sidereal time at Greenwich in degrees (mean or apparent).
+///
+/// The returned position is tagged [`Epoch::J2000`], use [`EqPosition::with_epoch`]
+/// to record the equinox the sidereal time refers to.
+#[must_use]
+pub fn get_equ_from_hrz_sidereal_time(
+    hrz: &HorizontalPosition,
+    observer: &LongLatPosition,
+    sidereal_time: f64,
+) -> EqPosition {
+    let azimuth_south = (hrz.azimuth - 180.0).to_radians();
+    let altitude = hrz.altitude.to_radians();
+    let lat = observer.lat_degrees().to_radians();
+
+    let hour_angle = azimuth_south
+        .sin()
+        .atan2(azimuth_south.cos() * lat.sin() + altitude.tan() * lat.cos());
+    let dec =
+        (lat.sin() * altitude.sin() - lat.cos() * altitude.cos() * azimuth_south.cos()).asin();
+
+    let ra = sidereal_time + observer.long_degrees() - hour_angle.to_degrees();
+
+    EqPosition::from_degrees(ra, dec.to_degrees())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Declination, RightAscension};
+
+    // Meeus, Astronomical Algorithms, 2nd ed., Example 13.b, p. 95:
+    // Venus from the US Naval Observatory on 1987 April 10, 19h21m00s UT.
+    fn venus() -> EqPosition {
+        EqPosition::new(
+            RightAscension::new(23, 9, 16.641),
+            Declination::new(-6, 43, 11.61),
+        )
+    }
+
+    fn usno() -> LongLatPosition {
+        LongLatPosition::new(
+            Declination::new(-77, 3, 56.0),
+            Declination::new(38, 55, 17.0),
+        )
+    }
+
+    #[test]
+    fn test_hrz_from_equ_meeus_example_13b() {
+        // Apparent sidereal time 8h34m56.853s, A = 68.0337° from the South, h = 15.1249°
+        let sidereal_time = RightAscension::new(8, 34, 56.853);
+        let hrz = get_hrz_from_equ_sidereal_time(
+            &venus(),
+            &usno(),
+            crate::transform::ra_to_deg(&sidereal_time),
+        );
+
+        assert!(
+            (hrz.azimuth - 248.0337).abs() < 0.000_2,
+            "azimuth: {}",
+            hrz.azimuth
+        );
+        assert!(
+            (hrz.altitude - 15.1249).abs() < 0.000_2,
+            "altitude: {}",
+            hrz.altitude
+        );
+    }
+
+    #[test]
+    fn test_hrz_from_equ_with_julian_day() {
+        // Mean instead of apparent sidereal time moves the result by a few arcseconds.
+        let jd = JulianDay::new(2_446_896.306_25);
+        let hrz = get_hrz_from_equ(&venus(), &usno(), &jd);
+
+        assert!(
+            (hrz.azimuth - 248.0337).abs() < 0.01,
+            "azimuth: {}",
+            hrz.azimuth
+        );
+        assert!(
+            (hrz.altitude - 15.1249).abs() < 0.01,
+            "altitude: {}",
+            hrz.altitude
+        );
+    }
+
+    #[test]
+    fn test_equ_from_hrz_round_trip() {
+        let jd = JulianDay::new(2_446_896.306_25);
+        let hrz = get_hrz_from_equ(&venus(), &usno(), &jd);
+        let pos = get_equ_from_hrz(&hrz, &usno(), &jd);
+
+        assert!((pos.ra_degrees() - venus().ra_degrees()).abs() < 1e-8);
+        assert!((pos.dec_degrees() - venus().dec_degrees()).abs() < 1e-8);
+        assert_eq!(pos.epoch(), Epoch::OfDate(jd));
+    }
+
+    #[test]
+    fn test_pole_star_altitude_equals_latitude() {
+        let pole = EqPosition::from_degrees(0.0, 90.0);
+        let hrz = get_hrz_from_equ_sidereal_time(&pole, &usno(), 123.0);
+
+        assert!((hrz.altitude - usno().lat_degrees()).abs() < 1e-9);
+        assert!(hrz.azimuth.abs() < 1e-9 || (hrz.azimuth - 360.0).abs() < 1e-9);
+    }
+}
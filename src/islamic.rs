@@ -0,0 +1,258 @@
+//! Arithmetical (tabular) Islamic calendar (Meeus ch. 9).
+//!
+//! The years have 12 months of alternately 30 and 29 days, and in 11 years
+//! of each cycle of 30 years the last month has 30 days instead of 29. The
+//! actual start of the months depends on the sighting of the lunar crescent
+//! and may differ by a day or two from this calendar.
+//!
+//! As for the Jewish calendar, days are counted from midnight, while the
+//! Islamic day begins at the preceding sunset.
+
+use crate::date::Date;
+use crate::julian_day::JulianDay;
+use crate::Error;
+
+/// Julian Day of the 0h of 1 Muharram 1 AH, 622 July 16 in the Julian calendar.
+const EPOCH_JD: f64 = 1_948_439.5;
+
+/// A date of the arithmetical Islamic calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IslamicDate {
+    /// Year of the Hijra (AH).
+    pub year: i32,
+    /// Month from 1 (Muharram) to 12 (Dhu al-Hijja), 9 is Ramadan.
+    pub month: u8,
+    pub day: u8,
+}
+
+impl IslamicDate {
+    /// First year that can be converted.
+    pub const MIN_YEAR: i32 = i32::MIN + 1;
+    /// Last year that can be converted.
+    pub const MAX_YEAR: i32 = i32::MAX - 1;
+
+    #[must_use]
+    pub fn new(year: i32, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+
+    /// Returns `true` for the years 2, 5, 7, 10, 13, 16, 18, 21, 24, 26 and 29
+    /// of the 30-year cycle (Meeus p. 73).
+    #[must_use]
+    pub fn is_leap_year(year: i32) -> bool {
+        (14 + 11 * year.rem_euclid(30)).rem_euclid(30) < 11
+    }
+
+    /// Number of days of the year, 354 or 355 for leap years.
+    #[must_use]
+    pub fn days_in_year(year: i32) -> u16 {
+        354 + u16::from(Self::is_leap_year(year))
+    }
+
+    /// Number of days of a month, 0 if the month is not in the range 1-12.
+    #[must_use]
+    pub fn days_in_month(year: i32, month: u8) -> u8 {
+        match month {
+            12 if Self::is_leap_year(year) => 30,
+            1..=12 if month % 2 == 1 => 30,
+            1..=12 => 29,
+            _ => 0,
+        }
+    }
+
+    /// Returns the Islamic date of the day containing a Julian Day.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the year is outside [`IslamicDate::MIN_YEAR`]..=[`IslamicDate::MAX_YEAR`],
+    /// see [`IslamicDate::try_from_julian_day`].
+    #[must_use]
+    pub fn from_julian_day(jd: &JulianDay) -> Self {
+        Self::try_from_julian_day(jd).expect("Islamic year out of range")
+    }
+
+    /// Fallible version of [`IslamicDate::from_julian_day`], returning
+    /// [`Error::YearOutOfRange`] if the Julian Day is not finite or its year is
+    /// outside [`IslamicDate::MIN_YEAR`]..=[`IslamicDate::MAX_YEAR`].
+    pub fn try_from_julian_day(jd: &JulianDay) -> Result<Self, Error> {
+        let midnight = (*jd + 0.5_f64).day() - 0.5_f64;
+        if midnight.is_nan() || midnight <= days_before(Self::MIN_YEAR, 1) {
+            return Err(Error::YearOutOfRange(Self::MIN_YEAR - 1));
+        }
+        if midnight > days_before(Self::MAX_YEAR + 1, 1) {
+            return Err(Error::YearOutOfRange(Self::MAX_YEAR + 1));
+        }
+
+        let year = ((30_f64 * (midnight - EPOCH_JD) + 10_646_f64) / 10_631_f64).floor() as i32;
+        let day_of_year = midnight - days_before(year, 1) - 1_f64;
+        let month = ((11_f64 * day_of_year + 330_f64) / 325_f64).floor() as u8;
+        let day = midnight - days_before(year, month);
+
+        Ok(Self::new(year, month, day as u8))
+    }
+
+    /// Returns the Islamic date of a calendar date, see [`IslamicDate::from_julian_day`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date is invalid, see [`IslamicDate::try_from_date`].
+    #[must_use]
+    pub fn from_date(date: &Date) -> Self {
+        Self::try_from_date(date).expect("Invalid date")
+    }
+
+    /// Fallible version of [`IslamicDate::from_date`].
+    pub fn try_from_date(date: &Date) -> Result<Self, Error> {
+        Self::try_from_julian_day(&date.try_to_julian_day()?)
+    }
+
+    /// Returns the Julian Day of the 0h of the date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date doesn't exist, see [`IslamicDate::try_to_julian_day`].
+    #[must_use]
+    pub fn to_julian_day(&self) -> JulianDay {
+        self.try_to_julian_day().expect("Invalid Islamic date")
+    }
+
+    /// Fallible version of [`IslamicDate::to_julian_day`], returning
+    /// [`Error::YearOutOfRange`] for a year outside
+    /// [`IslamicDate::MIN_YEAR`]..=[`IslamicDate::MAX_YEAR`], and
+    /// [`Error::InvalidMonth`] or [`Error::InvalidDay`] for a date that doesn't exist.
+    pub fn try_to_julian_day(&self) -> Result<JulianDay, Error> {
+        if !(Self::MIN_YEAR..=Self::MAX_YEAR).contains(&self.year) {
+            return Err(Error::YearOutOfRange(self.year));
+        }
+        let length = Self::days_in_month(self.year, self.month);
+        if length == 0 {
+            return Err(Error::InvalidMonth(self.month));
+        }
+        if self.day == 0 || self.day > length {
+            return Err(Error::InvalidDay(f64::from(self.day)));
+        }
+
+        Ok(JulianDay::new(
+            days_before(self.year, self.month) + f64::from(self.day),
+        ))
+    }
+
+    /// Returns the calendar date, see [`IslamicDate::to_julian_day`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date doesn't exist or its year doesn't fit a [`Date`],
+    /// see [`IslamicDate::try_to_date`].
+    #[must_use]
+    pub fn to_date(&self) -> Date {
+        self.try_to_date().expect("Invalid Islamic date")
+    }
+
+    /// Fallible version of [`IslamicDate::to_date`], see
+    /// [`IslamicDate::try_to_julian_day`] and [`Date::try_from_julian_day`].
+    pub fn try_to_date(&self) -> Result<Date, Error> {
+        Date::try_from_julian_day(&self.try_to_julian_day()?)
+    }
+}
+
+/// Julian Day of the 0h of the day before the first day of a month.
+fn days_before(year: i32, month: u8) -> f64 {
+    let (year, month) = (f64::from(year), i32::from(month));
+    EPOCH_JD - 1_f64
+        + 354_f64 * (year - 1_f64)
+        + ((3_f64 + 11_f64 * year) / 30_f64).floor()
+        + f64::from(29 * (month - 1) + month / 2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_islamic_dates_meeus() {
+        // From Meeus book "astronomical algorithms" p. 75 example 9.d
+        assert_eq!(
+            IslamicDate::from_date(&Date::new(1991, 8, 13.0)),
+            IslamicDate::new(1412, 2, 2)
+        );
+        assert_eq!(
+            IslamicDate::new(1421, 1, 1).to_date(),
+            Date::new(2000, 4, 6.0)
+        );
+        assert_eq!(
+            IslamicDate::new(1, 1, 1).to_julian_day(),
+            JulianDay::new(1_948_439.5)
+        );
+    }
+
+    #[test]
+    fn test_islamic_leap_years() {
+        let leap: Vec<i32> = (1..=30).filter(|y| IslamicDate::is_leap_year(*y)).collect();
+        assert_eq!(leap, [2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29]);
+        assert_eq!(IslamicDate::days_in_year(1412), 355);
+        assert_eq!(IslamicDate::days_in_month(1412, 12), 30);
+        assert_eq!(IslamicDate::days_in_month(1411, 12), 29);
+        // far years follow the same 30-year cycle without overflowing
+        assert!(IslamicDate::is_leap_year(i32::MAX));
+        assert_eq!(
+            IslamicDate::is_leap_year(i32::MIN),
+            IslamicDate::is_leap_year(i32::MIN.rem_euclid(30))
+        );
+    }
+
+    #[test]
+    fn test_islamic_round_trip() {
+        let start = Date::new(1990, 1, 1.0).to_julian_day();
+        let mut previous = IslamicDate::from_julian_day(&start);
+        for days in 1..12_000 {
+            let jd = start + f64::from(days);
+            let date = IslamicDate::from_julian_day(&jd);
+            assert_eq!(date.to_julian_day(), jd, "{date:?}");
+            assert!(date.day == previous.day + 1 || date.day == 1, "{date:?}");
+            previous = date;
+        }
+    }
+
+    #[test]
+    fn test_invalid_islamic_dates() {
+        assert_eq!(
+            IslamicDate::new(1411, 12, 30).try_to_julian_day(),
+            Err(Error::InvalidDay(30.0))
+        );
+        assert_eq!(
+            IslamicDate::new(1411, 13, 1).try_to_date(),
+            Err(Error::InvalidMonth(13))
+        );
+        // far years don't overflow
+        for year in [IslamicDate::MIN_YEAR, IslamicDate::MAX_YEAR] {
+            for far in [IslamicDate::new(year, 1, 1), IslamicDate::new(year, 12, 29)] {
+                assert_eq!(IslamicDate::from_julian_day(&far.to_julian_day()), far);
+            }
+        }
+        assert_eq!(
+            IslamicDate::new(i32::MAX, 12, 1).try_to_julian_day(),
+            Err(Error::YearOutOfRange(i32::MAX))
+        );
+        assert!(IslamicDate::new(IslamicDate::MAX_YEAR, 1, 1)
+            .try_to_date()
+            .is_err());
+    }
+
+    #[test]
+    fn test_islamic_julian_day_out_of_range() {
+        let first = IslamicDate::new(IslamicDate::MIN_YEAR, 1, 1).to_julian_day();
+        let year = IslamicDate::MAX_YEAR;
+        let last = IslamicDate::new(year, 12, IslamicDate::days_in_month(year, 12)).to_julian_day();
+        assert_eq!(
+            IslamicDate::try_from_julian_day(&(first - 1_f64)),
+            Err(Error::YearOutOfRange(i32::MIN))
+        );
+        assert_eq!(
+            IslamicDate::try_from_julian_day(&(last + 1_f64)),
+            Err(Error::YearOutOfRange(i32::MAX))
+        );
+        for jd in [f64::NAN, f64::INFINITY, 1e15] {
+            assert!(IslamicDate::try_from_julian_day(&JulianDay::new(jd)).is_err());
+        }
+    }
+}
@@ -0,0 +1,409 @@
+//! Jewish calendar (Meeus ch. 9).
+//!
+//! The date of Passover is computed with the method of Meeus p. 71, the
+//! New Year (1 Tishri) follows it by 163 days, and the lengths of the months
+//! are derived from the number of days between two New Years.
+//!
+//! Days are counted from midnight, while the Jewish day begins at the
+//! preceding sunset.
+
+use crate::date::{Calendar, Date};
+use crate::julian_day::JulianDay;
+use crate::Error;
+
+/// Difference between the Jewish year and the Christian year of its Passover.
+const YEAR_OFFSET: i32 = 3760;
+
+/// Months of the Jewish calendar numbered from Nisan, as in the Bible.
+///
+/// The civil year begins with Tishri. In leap years `Adar` is Adar I
+/// (30 days) and is followed by `AdarII`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JewishMonth {
+    Nisan = 1,
+    Iyyar,
+    Sivan,
+    Tammuz,
+    Av,
+    Elul,
+    Tishri,
+    Heshvan,
+    Kislev,
+    Tevet,
+    Shevat,
+    Adar,
+    AdarII,
+}
+
+impl JewishMonth {
+    /// Months of a year in civil order, from Tishri to Elul.
+    fn civil_order(leap: bool) -> impl Iterator<Item = Self> {
+        [
+            Self::Tishri,
+            Self::Heshvan,
+            Self::Kislev,
+            Self::Tevet,
+            Self::Shevat,
+            Self::Adar,
+            Self::AdarII,
+            Self::Nisan,
+            Self::Iyyar,
+            Self::Sivan,
+            Self::Tammuz,
+            Self::Av,
+            Self::Elul,
+        ]
+        .into_iter()
+        .filter(move |month| leap || *month != Self::AdarII)
+    }
+}
+
+/// A date of the Jewish calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JewishDate {
+    /// Year of the world (Anno Mundi), e.g. 5785 began in October 2024.
+    pub year: i32,
+    pub month: JewishMonth,
+    pub day: u8,
+}
+
+impl JewishDate {
+    /// First year that can be converted, starting in the autumn of -32768.
+    pub const MIN_YEAR: i32 = i16::MIN as i32 + YEAR_OFFSET + 1;
+    /// Last year that can be converted, ending in 32767. The Jewish calendar
+    /// drifts against the Gregorian one, by then Passover falls in November and
+    /// the next year would end in 32768.
+    pub const MAX_YEAR: i32 = i16::MAX as i32 + YEAR_OFFSET - 1;
+
+    #[must_use]
+    pub fn new(year: i32, month: JewishMonth, day: u8) -> Self {
+        Self { year, month, day }
+    }
+
+    /// Returns `true` if the year has 13 months (Meeus p. 73).
+    #[must_use]
+    pub fn is_leap_year(year: i32) -> bool {
+        (7 * year.rem_euclid(19) + 1).rem_euclid(19) < 7
+    }
+
+    /// Number of days of the year: 353, 354 or 355 for common years and 383,
+    /// 384 or 385 for leap years.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the year is outside [`JewishDate::MIN_YEAR`]..=[`JewishDate::MAX_YEAR`].
+    #[must_use]
+    pub fn days_in_year(year: i32) -> u16 {
+        (new_year(year + 1) - new_year(year)) as u16
+    }
+
+    /// Number of days of a month, 0 for Adar II in a common year.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the year is outside [`JewishDate::MIN_YEAR`]..=[`JewishDate::MAX_YEAR`].
+    #[must_use]
+    pub fn days_in_month(year: i32, month: JewishMonth) -> u8 {
+        let leap = Self::is_leap_year(year);
+        match month {
+            JewishMonth::AdarII if !leap => 0,
+            JewishMonth::Adar if leap => 30,
+            // complete years
+            JewishMonth::Heshvan if Self::days_in_year(year) % 10 == 5 => 30,
+            // deficient years
+            JewishMonth::Kislev if Self::days_in_year(year) % 10 == 3 => 29,
+            JewishMonth::Iyyar
+            | JewishMonth::Tammuz
+            | JewishMonth::Elul
+            | JewishMonth::Heshvan
+            | JewishMonth::Tevet
+            | JewishMonth::Adar
+            | JewishMonth::AdarII => 29,
+            _ => 30,
+        }
+    }
+
+    /// Returns the Jewish date of the day containing a Julian Day.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the year is out of range, see [`JewishDate::try_from_julian_day`].
+    #[must_use]
+    pub fn from_julian_day(jd: &JulianDay) -> Self {
+        Self::try_from_julian_day(jd).expect("Jewish year out of range")
+    }
+
+    /// Fallible version of [`JewishDate::from_julian_day`], returning
+    /// [`Error::YearOutOfRange`] if the year is outside
+    /// [`JewishDate::MIN_YEAR`]..=[`JewishDate::MAX_YEAR`].
+    pub fn try_from_julian_day(jd: &JulianDay) -> Result<Self, Error> {
+        let midnight = (*jd + 0.5_f64).day() - 0.5_f64;
+        if midnight.is_nan() || midnight < new_year(Self::MIN_YEAR) {
+            return Err(Error::YearOutOfRange(Self::MIN_YEAR - 1));
+        }
+        if midnight >= new_year(Self::MAX_YEAR + 1) {
+            return Err(Error::YearOutOfRange(Self::MAX_YEAR + 1));
+        }
+
+        // the New Year usually follows the Passover of the same Christian year,
+        // but the drift of the calendar moves it to the next one in the far future
+        let christian_year = i32::from(Date::from_julian_day(jd).year);
+        let mut year = (christian_year + YEAR_OFFSET).clamp(Self::MIN_YEAR, Self::MAX_YEAR);
+        while midnight < new_year(year) {
+            year -= 1;
+        }
+        while midnight >= new_year(year + 1) {
+            year += 1;
+        }
+
+        let mut days = (midnight - new_year(year)) as u16;
+        for month in JewishMonth::civil_order(Self::is_leap_year(year)) {
+            let length = u16::from(Self::days_in_month(year, month));
+            if days < length {
+                return Ok(Self::new(year, month, days as u8 + 1));
+            }
+            days -= length;
+        }
+        unreachable!("the day is before the next New Year")
+    }
+
+    /// Returns the Jewish date of a calendar date, see [`JewishDate::from_julian_day`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date is invalid or the year is out of range, see
+    /// [`JewishDate::try_from_date`].
+    #[must_use]
+    pub fn from_date(date: &Date) -> Self {
+        Self::try_from_date(date).expect("Invalid date")
+    }
+
+    /// Fallible version of [`JewishDate::from_date`].
+    pub fn try_from_date(date: &Date) -> Result<Self, Error> {
+        Self::try_from_julian_day(&date.try_to_julian_day()?)
+    }
+
+    /// Returns the Julian Day of the 0h of the date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date doesn't exist, see [`JewishDate::try_to_julian_day`].
+    #[must_use]
+    pub fn to_julian_day(&self) -> JulianDay {
+        self.try_to_julian_day().expect("Invalid Jewish date")
+    }
+
+    /// Fallible version of [`JewishDate::to_julian_day`], returning
+    /// [`Error::YearOutOfRange`] if the year is outside
+    /// [`JewishDate::MIN_YEAR`]..=[`JewishDate::MAX_YEAR`],
+    /// [`Error::InvalidMonth`] for Adar II in a common year and
+    /// [`Error::InvalidDay`] if the day is not in the month.
+    pub fn try_to_julian_day(&self) -> Result<JulianDay, Error> {
+        if !(Self::MIN_YEAR..=Self::MAX_YEAR).contains(&self.year) {
+            return Err(Error::YearOutOfRange(self.year));
+        }
+        let length = Self::days_in_month(self.year, self.month);
+        if length == 0 {
+            return Err(Error::InvalidMonth(self.month as u8));
+        }
+        if self.day == 0 || self.day > length {
+            return Err(Error::InvalidDay(f64::from(self.day)));
+        }
+
+        let days_before: u16 = JewishMonth::civil_order(Self::is_leap_year(self.year))
+            .take_while(|month| *month != self.month)
+            .map(|month| u16::from(Self::days_in_month(self.year, month)))
+            .sum();
+        Ok(JulianDay::new(
+            new_year(self.year) + f64::from(days_before) + f64::from(self.day - 1),
+        ))
+    }
+
+    /// Returns the calendar date, see [`JewishDate::to_julian_day`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date doesn't exist, see [`JewishDate::try_to_julian_day`].
+    #[must_use]
+    pub fn to_date(&self) -> Date {
+        Date::from_julian_day(&self.to_julian_day())
+    }
+
+    /// Fallible version of [`JewishDate::to_date`].
+    pub fn try_to_date(&self) -> Result<Date, Error> {
+        Ok(Date::from_julian_day(&self.try_to_julian_day()?))
+    }
+}
+
+/// Returns the date of Passover (15 Nisan) in a Christian year (Meeus p. 71),
+/// in the Gregorian calendar from 1583 and in the Julian calendar before.
+#[must_use]
+pub fn get_passover(year: i16) -> Date {
+    Date::from_julian_day(&JulianDay::new(passover(year)))
+}
+
+/// Returns the date of the Jewish New Year (1 Tishri) in the autumn of a
+/// Christian year, 163 days after Passover, see [`get_passover`].
+#[must_use]
+pub fn get_jewish_new_year(year: i16) -> Date {
+    Date::from_julian_day(&JulianDay::new(passover(year) + 163_f64))
+}
+
+/// Julian Day of the 0h of Passover in a Christian year (Meeus p. 71).
+fn passover(christian_year: i16) -> f64 {
+    let year = i32::from(christian_year);
+    let (s, calendar) = if year > 1582 {
+        (
+            (3 * year.div_euclid(100) - 5).div_euclid(4),
+            Calendar::ProlepticGregorian,
+        )
+    } else {
+        (0, Calendar::Julian)
+    };
+
+    let a = (12 * year + 12).rem_euclid(19);
+    let b = year.rem_euclid(4);
+    let q = -1.904_412_361_576_f64 + 1.554_241_796_621_f64 * f64::from(a) + 0.25_f64 * f64::from(b)
+        - 0.003_177_794_022_f64 * f64::from(year)
+        + f64::from(s);
+    let q_int = q.floor();
+    let j = (q_int as i32 + 3 * year + 5 * b + 2 - s).rem_euclid(7);
+    let r = q - q_int;
+
+    let march_day = if j == 2 || j == 4 || j == 6 {
+        q_int + 23_f64
+    } else if j == 1 && a > 6 && r >= 0.632_870_370 {
+        q_int + 24_f64
+    } else if j == 0 && a > 11 && r >= 0.897_723_765 {
+        q_int + 23_f64
+    } else {
+        q_int + 22_f64
+    };
+
+    let march = Date::new(christian_year, 3, 1.0).with_calendar(calendar);
+    march.to_julian_day().get_value() + march_day - 1_f64
+}
+
+/// Julian Day of the 0h of 1 Tishri of a Jewish year, 163 days after the
+/// Passover of the previous year.
+///
+/// Panics if the year is outside `MIN_YEAR..=MAX_YEAR + 1`.
+fn new_year(year: i32) -> f64 {
+    let christian_year = i16::try_from(year - YEAR_OFFSET - 1).expect("Jewish year out of range");
+    passover(christian_year) + 163_f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_passover_meeus() {
+        // From Meeus book "astronomical algorithms" p. 72 example 9.a
+        assert_eq!(get_passover(1990), Date::new(1990, 4, 10.0));
+        assert_eq!(get_jewish_new_year(1990), Date::new(1990, 9, 20.0));
+        assert_eq!(get_passover(2000), Date::new(2000, 4, 20.0));
+        assert_eq!(get_jewish_new_year(2024), Date::new(2024, 10, 3.0));
+        // in the Julian calendar before 1583
+        assert_eq!(get_passover(1243), Date::new(1243, 4, 7.0));
+    }
+
+    #[test]
+    fn test_year_lengths() {
+        assert_eq!(JewishDate::days_in_year(5750), 355);
+        assert_eq!(JewishDate::days_in_year(5751), 354);
+        assert!(JewishDate::is_leap_year(5752));
+        assert_eq!(
+            JewishDate::is_leap_year(i32::MAX),
+            JewishDate::is_leap_year(i32::MAX.rem_euclid(19))
+        );
+        assert_eq!(JewishDate::days_in_year(5752), 385);
+        assert_eq!(JewishDate::days_in_month(5752, JewishMonth::Adar), 30);
+        assert_eq!(JewishDate::days_in_month(5751, JewishMonth::AdarII), 0);
+        for year in 5700..5800 {
+            assert!(matches!(
+                JewishDate::days_in_year(year),
+                353..=355 | 383..=385
+            ));
+        }
+    }
+
+    #[test]
+    fn test_jewish_dates() {
+        let passover = JewishDate::new(5760, JewishMonth::Nisan, 15);
+        assert_eq!(JewishDate::from_date(&Date::new(2000, 4, 20.0)), passover);
+        assert_eq!(passover.to_date(), Date::new(2000, 4, 20.0));
+
+        // Yom Kippur 5785 and Purim of the leap year 5784
+        assert_eq!(
+            JewishDate::new(5785, JewishMonth::Tishri, 10).to_date(),
+            Date::new(2024, 10, 12.0)
+        );
+        assert_eq!(
+            JewishDate::from_date(&Date::new(2024, 3, 24.0)),
+            JewishDate::new(5784, JewishMonth::AdarII, 14)
+        );
+
+        // the day containing an instant
+        let evening = Date::from_full_date(2024, 10, 2.0, 23, 59, 0.0).to_julian_day();
+        assert_eq!(
+            JewishDate::from_julian_day(&evening),
+            JewishDate::new(5784, JewishMonth::Elul, 29)
+        );
+    }
+
+    #[test]
+    fn test_jewish_round_trip() {
+        let start = Date::new(1989, 1, 1.0).to_julian_day();
+        let mut previous = JewishDate::from_julian_day(&start);
+        for days in 1..1500 {
+            let jd = start + f64::from(days);
+            let date = JewishDate::from_julian_day(&jd);
+            assert_eq!(date.to_julian_day(), jd, "{date:?}");
+            assert!(date.day == previous.day + 1 || date.day == 1, "{date:?}");
+            previous = date;
+        }
+    }
+
+    #[test]
+    fn test_invalid_jewish_dates() {
+        assert_eq!(
+            JewishDate::new(5751, JewishMonth::AdarII, 1).try_to_julian_day(),
+            Err(Error::InvalidMonth(13))
+        );
+        assert_eq!(
+            JewishDate::new(5751, JewishMonth::Iyyar, 30).try_to_julian_day(),
+            Err(Error::InvalidDay(30.0))
+        );
+        assert!(JewishDate::new(5751, JewishMonth::Nisan, 0)
+            .try_to_date()
+            .is_err());
+    }
+
+    #[test]
+    fn test_jewish_year_range() {
+        let first = JewishDate::new(JewishDate::MIN_YEAR, JewishMonth::Tishri, 1);
+        let last = JewishDate::new(JewishDate::MAX_YEAR, JewishMonth::Elul, 29);
+        for date in [first, last] {
+            assert_eq!(JewishDate::from_julian_day(&date.to_julian_day()), date);
+        }
+        // near the end Passover is in November and the New Year in the next year
+        for days in 0..1000 {
+            let jd = last.to_julian_day() - f64::from(days);
+            assert_eq!(JewishDate::from_julian_day(&jd).to_julian_day(), jd);
+        }
+        assert_eq!(
+            JewishDate::new(40_000, JewishMonth::Nisan, 1).try_to_julian_day(),
+            Err(Error::YearOutOfRange(40_000))
+        );
+        assert_eq!(
+            JewishDate::try_from_julian_day(&(last.to_julian_day() + 1_f64)),
+            Err(Error::YearOutOfRange(JewishDate::MAX_YEAR + 1))
+        );
+        assert_eq!(
+            JewishDate::try_from_julian_day(&(first.to_julian_day() - 1_f64)),
+            Err(Error::YearOutOfRange(JewishDate::MIN_YEAR - 1))
+        );
+        assert!(JewishDate::try_from_julian_day(&JulianDay::new(f64::NAN)).is_err());
+    }
+}
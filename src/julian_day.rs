@@ -1,109 +1,332 @@
-use crate::date::Date;
+use std::ops::{Add, Sub};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, PartialEq, PartialOrd)]
+use crate::date::{Calendar, Date};
+use crate::duration::Duration;
+use crate::dynamical_time::try_delta_t;
+use crate::Error;
+
+/// Whole Julian Day of the Unix epoch, 1970-01-01T00:00:00Z is at its fraction 0.5.
+const UNIX_EPOCH_JD: f64 = 2_440_587_f64;
+
+/// A Julian Day stored as a whole number of days and a fraction of day in the
+/// range [0, 1), which keeps sub-microsecond resolution near the present epoch
+/// where a single `f64` is limited to about 40 µs.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct JulianDay {
-    value: f64,
+    day: f64,
+    fraction: f64,
 }
 
 impl JulianDay {
     #[must_use]
     pub fn new(value: f64) -> Self {
-        Self { value }
+        Self::from_parts(value, 0_f64)
     }
 
+    /// Creates a Julian Day from the sum of two parts, typically a whole day and
+    /// a fraction of day, which are normalised; neither needs to be in range.
+    #[must_use]
+    pub fn from_parts(day: f64, fraction: f64) -> Self {
+        let whole = day.floor() + fraction.floor();
+        let fraction = (day - day.floor()) + (fraction - fraction.floor());
+        let carry = fraction.floor();
+
+        Self {
+            day: whole + carry,
+            fraction: fraction - carry,
+        }
+    }
+
+    /// Returns the Julian Day as a single `f64`.
     #[must_use]
     pub fn get_value(&self) -> f64 {
-        self.value
+        self.day + self.fraction
     }
 
+    /// Whole number of days, the Julian Day of the preceding noon.
     #[must_use]
-    pub fn from_date(date: &Date) -> Self {
-        Self {
-            value: get_julian_day(date),
+    pub fn day(&self) -> f64 {
+        self.day
+    }
+
+    /// Fraction of day since the preceding noon, in the range [0, 1).
+    #[must_use]
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// Returns the number of days elapsed since `other`, without the loss of
+    /// precision of subtracting the values of [`JulianDay::get_value`].
+    #[must_use]
+    pub fn days_since(&self, other: &Self) -> f64 {
+        (self.day - other.day) + (self.fraction - other.fraction)
+    }
+
+    /// Creates a Julian Day (UTC) from a Unix time, the number of seconds since
+    /// 1970-01-01T00:00:00Z ignoring leap seconds.
+    #[must_use]
+    pub fn from_unix(seconds: f64) -> Self {
+        let days = (seconds / 86400_f64).floor();
+        let rest = seconds - days * 86400_f64;
+        Self::from_parts(UNIX_EPOCH_JD + days, 0.5_f64 + rest / 86400_f64)
+    }
+
+    /// Returns the Unix time of this Julian Day (UTC).
+    #[must_use]
+    pub fn to_unix(&self) -> f64 {
+        ((self.day - UNIX_EPOCH_JD) + (self.fraction - 0.5_f64)) * 86400_f64
+    }
+
+    /// Creates a Julian Day (UTC) from a [`SystemTime`].
+    #[must_use]
+    pub fn from_system_time(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => {
+                Self::from_unix(elapsed.as_secs() as f64)
+                    + f64::from(elapsed.subsec_nanos()) / 86_400e9
+            }
+            Err(e) => {
+                let before = e.duration();
+                Self::from_unix(-(before.as_secs() as f64))
+                    - f64::from(before.subsec_nanos()) / 86_400e9
+            }
         }
     }
 
+    /// Julian Day (UTC) of the system clock.
+    #[must_use]
+    pub fn now() -> Self {
+        Self::from_system_time(SystemTime::now())
+    }
+
+    #[must_use]
+    pub fn from_date(date: &Date) -> Self {
+        Self::try_from_date(date).expect("Error")
+    }
+
+    /// Fallible version of [`JulianDay::from_date`].
+    pub fn try_from_date(date: &Date) -> Result<Self, Error> {
+        let (day, fraction) = julian_day_parts(date)?;
+        Ok(Self::from_parts(day, fraction))
+    }
+
     #[must_use]
     pub fn to_modified_jd(&self) -> f64 {
-        self.value - 2_400_000.5_f64
+        (self.day - 2_400_000.5_f64) + self.fraction
+    }
+
+    /// Returns the Julian Ephemeris Day (TD) of this Julian Day (UT),
+    /// JDE = JD + ΔT/86400 with ΔT from [`crate::dynamical_time::delta_t`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if ΔT is not available for the date, see [`JulianDay::try_to_jde`].
+    #[must_use]
+    pub fn to_jde(&self) -> Self {
+        self.try_to_jde().expect("Not supported")
+    }
+
+    /// Fallible version of [`JulianDay::to_jde`].
+    pub fn try_to_jde(&self) -> Result<Self, Error> {
+        let delta_t = try_delta_t(&self.try_to_calendar_date()?)?;
+        Ok(self.to_jde_with_delta_t(delta_t))
+    }
+
+    /// Returns the Julian Ephemeris Day of this Julian Day (UT) for a known ΔT in seconds.
+    #[must_use]
+    pub fn to_jde_with_delta_t(&self, delta_t: f64) -> Self {
+        *self + delta_t / 86400_f64
+    }
+
+    /// Returns the Julian Day (UT) of a Julian Ephemeris Day (TD), ΔT being
+    /// evaluated at the JDE.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ΔT is not available for the date, see [`JulianDay::try_from_jde`].
+    #[must_use]
+    pub fn from_jde(jde: &Self) -> Self {
+        Self::try_from_jde(jde).expect("Not supported")
+    }
+
+    /// Fallible version of [`JulianDay::from_jde`].
+    pub fn try_from_jde(jde: &Self) -> Result<Self, Error> {
+        let delta_t = try_delta_t(&jde.try_to_calendar_date()?)?;
+        Ok(Self::from_jde_with_delta_t(jde, delta_t))
+    }
+
+    /// Returns the Julian Day (UT) of a Julian Ephemeris Day for a known ΔT in seconds.
+    #[must_use]
+    pub fn from_jde_with_delta_t(jde: &Self, delta_t: f64) -> Self {
+        *jde - delta_t / 86400_f64
     }
 
+    /// Returns the calendar date of the Julian Day in the [`Calendar::Gregorian`]
+    /// calendar, the time of day being the fraction of `day`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the year doesn't fit a [`Date`], see [`JulianDay::try_to_calendar_date`].
     #[must_use]
     pub fn to_calendar_date(&self) -> Date {
-        let jd_plus_half = self.value + 0.5_f64;
-        let z: i32 = jd_plus_half as i32;
-        let f: f64 = jd_plus_half - f64::from(z);
+        self.to_calendar_date_with(Calendar::default())
+    }
 
-        let a: i32 = if z < 2_299_161 {
-            z
+    /// Fallible version of [`JulianDay::to_calendar_date`].
+    pub fn try_to_calendar_date(&self) -> Result<Date, Error> {
+        self.try_to_calendar_date_with(Calendar::default())
+    }
+
+    /// Returns the calendar date of the Julian Day in the given calendar (Meeus ch. 7, p. 63).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the year doesn't fit a [`Date`], see [`JulianDay::try_to_calendar_date_with`].
+    #[must_use]
+    pub fn to_calendar_date_with(&self, calendar: Calendar) -> Date {
+        self.try_to_calendar_date_with(calendar)
+            .expect("Year out of range")
+    }
+
+    /// Fallible version of [`JulianDay::to_calendar_date_with`], returning
+    /// [`Error::YearOutOfRange`] if the year is outside the range of `i16`
+    /// and [`Error::InvalidDay`] if the Julian Day is not a number.
+    pub fn try_to_calendar_date_with(&self, calendar: Calendar) -> Result<Date, Error> {
+        let jd_plus_half = Self::from_parts(self.day, self.fraction + 0.5_f64);
+        let z = jd_plus_half.day;
+        let f = jd_plus_half.fraction;
+
+        let a = if calendar.is_gregorian(z - 0.5_f64) {
+            let alfa = ((z - 1_867_216.25_f64) / 36524.25).floor();
+            z + 1_f64 + alfa - (alfa / 4_f64).floor()
         } else {
-            let alfa = ((f64::from(z) - 1_867_216.25_f64) / 36524.25) as i32;
-            z + 1 + alfa - (f64::from(alfa) / 4_f64) as i32
+            z
         };
 
-        let b: i32 = a + 1524;
-        let c: i16 = ((f64::from(b) - 122.1_f64) / 365.25) as i16;
-        let d: i32 = (365.25_f64 * f64::from(c)) as i32;
-        let e: u8 = (f64::from(b - d) / 30.6001_f64) as u8;
+        let b = a + 1524_f64;
+        let c = ((b - 122.1_f64) / 365.25).floor();
+        let d = (365.25_f64 * c).floor();
+        let e = ((b - d) / 30.6001_f64).floor();
 
-        let day: f64 = f64::from(b - d - (30.6001_f64 * f64::from(e)) as i32) + f;
+        let day = b - d - (30.6001_f64 * e).floor() + f;
 
-        let month: u8 = {
-            if e < 14 {
-                e - 1
+        let month = {
+            if e < 14_f64 {
+                e - 1_f64
             } else {
-                e - 13
+                e - 13_f64
             }
         };
 
-        let year: i16 = {
-            if month > 2 {
-                c - 4716
+        let year = {
+            if month > 2_f64 {
+                c - 4716_f64
             } else {
-                c - 4715
+                c - 4715_f64
             }
         };
 
-        Date::new(year, month, day)
+        if year.is_nan() {
+            return Err(Error::InvalidDay(day));
+        }
+        let year = i16::try_from(year as i64).map_err(|_| Error::YearOutOfRange(year as i32))?;
+
+        Ok(Date::new(year, month as u8, day).with_calendar(calendar))
+    }
+}
+
+impl Add<f64> for JulianDay {
+    type Output = Self;
+
+    /// Adds a number of days.
+    fn add(self, days: f64) -> Self {
+        Self::from_parts(self.day, self.fraction + days)
+    }
+}
+
+impl Sub<f64> for JulianDay {
+    type Output = Self;
+
+    /// Subtracts a number of days.
+    fn sub(self, days: f64) -> Self {
+        Self::from_parts(self.day, self.fraction - days)
     }
 }
 
+impl Add<Duration> for JulianDay {
+    type Output = Self;
+
+    fn add(self, duration: Duration) -> Self {
+        self + duration.days()
+    }
+}
+
+impl Sub<Duration> for JulianDay {
+    type Output = Self;
+
+    fn sub(self, duration: Duration) -> Self {
+        self - duration.days()
+    }
+}
+
+impl Sub for JulianDay {
+    type Output = Duration;
+
+    /// Returns the signed span from `other` to `self`, see [`JulianDay::days_since`].
+    fn sub(self, other: Self) -> Duration {
+        Duration::from_days(self.days_since(&other))
+    }
+}
+
+/// Returns the Julian Day of a date in its calendar (Meeus eq. 7.1).
+///
+/// # Panics
+///
+/// Panics if the date is invalid, see [`try_get_julian_day`].
 #[must_use]
 pub fn get_julian_day(date: &Date) -> f64 {
-    let year;
-    let month;
+    try_get_julian_day(date).expect("Error")
+}
 
-    match date.month {
-        3..=12 => {
-            year = date.year;
-            month = date.month;
-        }
-        1..=2 => {
-            year = date.year - 1;
-            month = date.month + 12;
-        }
-        _ => panic!("Error"),
+/// Returns the Julian Day of a date in its calendar, [`Error::InvalidMonth`]
+/// if the month of `date` is outside the range 1-12 or [`Error::InvalidDay`]
+/// if the day doesn't exist in the month, see [`Calendar::is_valid_date`].
+///
+/// The time of day is the sum of the fractional `day` and of `hms`.
+pub fn try_get_julian_day(date: &Date) -> Result<f64, Error> {
+    let (day, fraction) = julian_day_parts(date)?;
+    Ok(day + fraction)
+}
+
+/// Whole and fractional parts of the Julian Day of a calendar date.
+fn julian_day_parts(date: &Date) -> Result<(f64, f64), Error> {
+    if !(1..=12).contains(&date.month) {
+        return Err(Error::InvalidMonth(date.month));
+    }
+    if !date.is_valid() {
+        return Err(Error::InvalidDay(date.day));
     }
 
-    let b: i16 =
-        if year > 1582 || (year == 1582 && (month > 10 || (month == 10 && date.day >= 4.0))) {
-            // Gregorian calendar
-            2 - (year / 100) + ((year / 100) / 4)
-        } else {
-            // Julian calendar
-            0
-        };
+    let base = date
+        .calendar
+        .month_base(date.year, date.month, date.day)
+        .ok_or(Error::InvalidDay(date.day))?;
 
-    let left_side = (365.25_f64 * f64::from(year + 4716)) as i64;
-    let right_side = (30.6001_f64 * f64::from(month + 1)) as i64;
+    let day_fraction = (f64::from(date.hms.hours) * 3600_f64
+        + f64::from(date.hms.minutes) * 60_f64
+        + date.hms.seconds)
+        / 86400_f64;
 
-    left_side as f64 + right_side as f64 + date.day + f64::from(b) - 1524.5_f64
+    Ok((base, date.day + 0.5_f64 + day_fraction))
 }
 
 #[cfg(test)]
 mod test {
-    use crate::date::Date;
-    use crate::julian_day::{get_julian_day, JulianDay};
+    use crate::date::{Calendar, Date};
+    use crate::duration::Duration;
+    use crate::julian_day::{get_julian_day, try_get_julian_day, JulianDay};
+    use crate::Error;
 
     #[test]
     fn test_sputnik_launch_date_to_julian_date() {
@@ -135,6 +358,20 @@ mod test {
         assert_eq!(1_356_001.0, get_julian_day(&date));
     }
 
+    #[test]
+    fn test_hms_is_added_to_the_day() {
+        // Meeus example 12.b: 1987 April 10 at 19h21m00s UT
+        let date = Date::from_full_date(1987, 4, 10.0, 19, 21, 0.0);
+        assert!((get_julian_day(&date) - 2_446_896.306_25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_invalid_month_returns_error() {
+        let date = Date::new(1999, 13, 1.0);
+        assert_eq!(try_get_julian_day(&date), Err(Error::InvalidMonth(13)));
+        assert!(JulianDay::try_from_date(&Date::new(1999, 0, 1.0)).is_err());
+    }
+
     #[test]
     fn test_whole_jd_to_calendar_date() {
         // 2459581 is 2022-01-01 12:00:00 UTC
@@ -172,6 +409,143 @@ mod test {
         assert_eq!(math::round::half_up(date2.day, 2), 28.63);
     }
 
+    #[test]
+    fn test_jd_to_jde() {
+        // From Meeus book "astronomical algorithms" p. 78 example 10.a
+        // 1977 February 18 at 3h37m40s TD, ΔT = 48s
+        let jde = JulianDay::new(2_443_192.651_157_4);
+        let jd = JulianDay::from_jde_with_delta_t(&jde, 48.0);
+        assert!((jd.get_value() - 2_443_192.650_601_9).abs() < 1e-7);
+        assert_eq!(jd.to_jde_with_delta_t(48.0), jde);
+
+        let jd = JulianDay::from_jde(&jde);
+        assert!(((jde.get_value() - jd.get_value()) * 86400.0 - 48.0).abs() < 0.5);
+        assert!((jd.to_jde().get_value() - jde.get_value()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_calendars() {
+        let reform = JulianDay::new(2_299_160.5);
+        assert_eq!(reform.to_calendar_date(), Date::new(1582, 10, 15.0));
+        assert_eq!(
+            (reform - 1_f64).to_calendar_date(),
+            Date::new(1582, 10, 4.0)
+        );
+        assert_eq!(
+            reform.to_calendar_date_with(Calendar::Julian),
+            Date::new(1582, 10, 5.0).with_calendar(Calendar::Julian)
+        );
+
+        let date = Date::new(-4713, 11, 24.5).with_calendar(Calendar::ProlepticGregorian);
+        assert_eq!(date.to_julian_day().get_value(), 0.0);
+        assert_eq!(
+            JulianDay::new(0.0).to_calendar_date_with(Calendar::ProlepticGregorian),
+            date
+        );
+    }
+
+    #[test]
+    fn test_negative_julian_day() {
+        // 288 Julian years before -4712-03-01, JD 59.5
+        let date = Date::new(-5000, 3, 1.0);
+        assert_eq!(date.to_julian_day().get_value(), -105_132.5);
+        assert_eq!(JulianDay::new(-105_132.5).to_calendar_date(), date);
+    }
+
+    #[test]
+    fn test_calendar_date_out_of_range() {
+        let last = Date::new(i16::MAX, 12, 31.0).to_julian_day();
+        assert_eq!(
+            last.try_to_calendar_date(),
+            Ok(Date::new(i16::MAX, 12, 31.0))
+        );
+        assert_eq!(
+            (last + 1_f64).try_to_calendar_date(),
+            Err(Error::YearOutOfRange(32_768))
+        );
+        let first = Date::new(i16::MIN, 1, 1.0).to_julian_day();
+        assert_eq!(
+            (first - 1_f64).try_to_calendar_date_with(Calendar::Julian),
+            Err(Error::YearOutOfRange(-32_769))
+        );
+        assert!(JulianDay::new(1e15).try_to_calendar_date().is_err());
+        assert!(JulianDay::new(f64::NAN).try_to_calendar_date().is_err());
+    }
+
+    #[test]
+    fn test_duration_arithmetic() {
+        let jd = JulianDay::new(2_451_545.0);
+        let later = jd + Duration::from_hours(36.0);
+        assert_eq!(later.get_value(), 2_451_546.5);
+        assert_eq!(later - jd, Duration::from_days(1.5));
+        assert_eq!(later - Duration::from_days(1.5), jd);
+        assert!((jd - later).is_negative());
+    }
+
+    #[test]
+    fn test_jde_invalid_returns_error() {
+        assert!(JulianDay::new(f64::NAN).try_to_jde().is_err());
+        assert!(JulianDay::try_from_jde(&JulianDay::new(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn test_two_part_precision() {
+        let jd = JulianDay::from_parts(2_460_000.0, 0.25);
+        let later = jd + 1e-6 / 86400.0;
+        assert!(later > jd);
+        assert!((later.days_since(&jd) * 86400.0 - 1e-6).abs() < 1e-12);
+        assert_eq!(later - 1e-6 / 86400.0, jd);
+
+        let jd = JulianDay::from_parts(2_460_000.75, 0.5);
+        assert_eq!(jd.day(), 2_460_001.0);
+        assert_eq!(jd.fraction(), 0.25);
+        assert_eq!(jd.get_value(), 2_460_001.25);
+        assert_eq!(JulianDay::new(2_460_001.25), jd);
+        assert_eq!((jd - 0.5).day(), 2_460_000.0);
+    }
+
+    #[test]
+    fn test_date_to_julian_day_keeps_microseconds() {
+        let date = Date::from_full_date(2024, 3, 20.0, 3, 6, 21.000_001);
+        let jd = JulianDay::from_date(&date);
+        let reference = JulianDay::from_date(&Date::from_full_date(2024, 3, 20.0, 3, 6, 21.0));
+        assert!((jd.days_since(&reference) * 86400.0 - 1e-6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unix_time() {
+        assert_eq!(JulianDay::from_unix(0.0).get_value(), 2_440_587.5);
+        assert_eq!(JulianDay::new(2_440_587.5).to_unix(), 0.0);
+
+        // 2000-01-01T12:00:00Z
+        let j2000 = JulianDay::from_unix(946_728_000.0);
+        assert_eq!(j2000, JulianDay::new(2_451_545.0));
+        assert_eq!(j2000.to_unix(), 946_728_000.0);
+
+        let before = JulianDay::from_unix(-86_400.0 * 1.5);
+        assert_eq!(before.get_value(), 2_440_586.0);
+        assert_eq!(before.to_unix(), -129_600.0);
+
+        let precise = JulianDay::from_unix(1_700_000_000.000_001);
+        assert!((precise.to_unix() - 1_700_000_000.000_001).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_system_time() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let time = UNIX_EPOCH + Duration::new(946_728_000, 500_000_000);
+        let jd = JulianDay::from_system_time(time);
+        assert!((jd.days_since(&JulianDay::new(2_451_545.0)) * 86400.0 - 0.5).abs() < 1e-9);
+
+        let time = UNIX_EPOCH - Duration::new(86_400, 250_000_000);
+        let jd = JulianDay::from_system_time(time);
+        assert!((jd.to_unix() + 86_400.25).abs() < 1e-6);
+
+        // The clock is after 2024-01-01 on any machine running the tests.
+        assert!(JulianDay::now() > JulianDay::new(2_460_310.5));
+    }
+
     #[test]
     fn test_jd_to_modified_jd() {
         let jd = JulianDay::new(2_436_116.31);
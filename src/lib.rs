@@ -1,15 +1,38 @@
-use std::fmt::{Display, Formatter, Result};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 
-use regex::Regex;
+use error::{ParseError, ParseErrorKind};
+use parse::{parse_sexagesimal, Unit};
 
+pub mod aberration;
+pub mod apparent;
 pub mod date;
+pub mod duration;
 pub mod dynamical_time;
+pub mod easter;
+pub mod ecliptic;
+pub mod ephemeris;
+pub mod error;
+pub mod format;
+pub mod galactic;
+pub mod horizontal;
+pub mod islamic;
+pub mod jewish;
 pub mod julian_day;
+pub mod local_date;
 pub mod moon;
 pub mod nutation;
+mod parse;
+pub mod precession;
 pub mod sidereal_time;
+pub mod sun;
+pub mod time_scale;
+pub mod timezone;
 pub mod transform;
 
+pub use error::Error;
+use julian_day::JulianDay;
+
 /// Rework a big angle so it can fit in the standard range 0-360
 fn fit_degrees(orig_angle: f64) -> f64 {
     if (0f64..360f64).contains(&orig_angle) {
@@ -23,7 +46,7 @@ fn fit_degrees(orig_angle: f64) -> f64 {
 
 /// Representation of right ascension coordinates (or RA shortly)
 /// in hours, minutes and seconds.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct HoursMinSec {
     pub hours: u8,
     pub minutes: u8,
@@ -48,26 +71,68 @@ impl HoursMinSec {
     /// Tries to parse a RA position from a string
     /// DRAGONS AHEAD!
     /// Using this method implies you possibly already knows that the format
-    /// is accepted, this method panics if the string doesn't match the regex.
+    /// is accepted, this method panics if the string cannot be parsed.
+    /// Use [`HoursMinSec::try_from_string`] or `str::parse` to handle bad input.
     #[must_use]
     pub fn from_string(s: &str) -> Self {
-        let num_spaces_reg = Regex::new(r"^(\d{2}) (\d{2}) (\d{2}(\.\d{1,2})?)$").unwrap();
-        let caps = num_spaces_reg.captures(s);
+        Self::try_from_string(s).expect("Cannot parse RA string")
+    }
 
-        if let Some(mat) = caps {
-            return Self {
-                hours: mat.get(1).unwrap().as_str().parse::<u8>().unwrap(),
-                minutes: mat.get(2).unwrap().as_str().parse::<u8>().unwrap(),
-                seconds: mat.get(3).unwrap().as_str().parse::<f64>().unwrap(),
-            };
+    /// Parses a RA position from a string, returning
+    /// [`Error::InvalidRightAscension`] if the string cannot be parsed.
+    ///
+    /// Accepted notations include `12 34 56.7`, `12:34:56.789`, `12h34m56.7s`,
+    /// `12 34.5` and decimal hours such as `12.5824`. A decimal value followed by
+    /// a degree marker (`188.736°` or `188.736d`) is read as degrees.
+    pub fn try_from_string(s: &str) -> Result<Self, Error> {
+        let error =
+            |position, kind| Error::InvalidRightAscension(ParseError::new(s, position, kind));
+        let parsed = parse_sexagesimal(s).map_err(Error::InvalidRightAscension)?;
+
+        if parsed.negative {
+            return Err(error(
+                parsed.sign_position,
+                ParseErrorKind::NegativeRightAscension,
+            ));
         }
 
-        panic!("Cannot parse RA string")
+        if parsed.unit == Some(Unit::Degrees) {
+            if parsed.value() >= 360_f64 {
+                return Err(error(parsed.whole_position, ParseErrorKind::OutOfRange));
+            }
+            let total_secs = parsed.value() * 240_f64;
+            let hours = (total_secs / 3600_f64).trunc();
+            let minutes = ((total_secs - hours * 3600_f64) / 60_f64).trunc();
+
+            return Ok(Self::new(
+                hours as u8,
+                minutes as u8,
+                total_secs - hours * 3600_f64 - minutes * 60_f64,
+            ));
+        }
+
+        if parsed.whole >= 24 {
+            return Err(error(parsed.whole_position, ParseErrorKind::OutOfRange));
+        }
+
+        Ok(Self::new(
+            parsed.whole as u8,
+            parsed.minutes,
+            parsed.seconds,
+        ))
+    }
+}
+
+impl FromStr for HoursMinSec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_string(s)
     }
 }
 
 impl Display for HoursMinSec {
-    fn fmt(&self, f: &mut Formatter) -> Result {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "{}h {}m {}s", self.hours, self.minutes, self.seconds)
     }
 }
@@ -83,7 +148,7 @@ impl PartialEq for HoursMinSec {
 impl Eq for RightAscension {}
 
 /// Representation of a position in degrees, minutes and seconds.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct DegMinSec {
     pub negative: bool,
     pub degrees: i16,
@@ -121,26 +186,49 @@ impl DegMinSec {
     /// Tries to parse a DMS position from a string
     /// DRAGONS AHEAD!
     /// Using this method implies you possibly already knows that the format
-    /// is accepted, this method panics if the string doesn't match the regex.
+    /// is accepted, this method panics if the string cannot be parsed.
+    /// Use [`DegMinSec::try_from_string`] or `str::parse` to handle bad input.
     #[must_use]
     pub fn from_string(s: &str) -> Self {
-        let num_spaces_reg = Regex::new(r"^(-?\d{2}) (\d{2}) (\d{2}(\.\d{1,2})?)$").unwrap();
-        let caps = num_spaces_reg.captures(s);
+        Self::try_from_string(s).expect("Cannot parse DMS string")
+    }
 
-        if let Some(mat) = caps {
-            return Self::new(
-                mat.get(1).unwrap().as_str().parse::<i16>().unwrap(),
-                mat.get(2).unwrap().as_str().parse::<u8>().unwrap(),
-                mat.get(3).unwrap().as_str().parse::<f64>().unwrap(),
-            );
+    /// Parses a DMS position from a string, returning
+    /// [`Error::InvalidDegMinSec`] if the string cannot be parsed.
+    ///
+    /// Accepted notations include `-28 09 44.08`, `+41:16:09`, `+41°16'09"`,
+    /// `41d16m09s`, `-00 30 00` and decimal degrees such as `-28.1623`.
+    pub fn try_from_string(s: &str) -> Result<Self, Error> {
+        let parsed = parse_sexagesimal(s).map_err(Error::InvalidDegMinSec)?;
+
+        let error = |position, kind| Error::InvalidDegMinSec(ParseError::new(s, position, kind));
+        if parsed.whole > 360 {
+            return Err(error(parsed.whole_position, ParseErrorKind::OutOfRange));
+        }
+        if let (Some(Unit::Hours), Some(position)) = (parsed.unit, parsed.unit_position) {
+            let marker = s[position..].chars().next().unwrap_or('h');
+            return Err(error(position, ParseErrorKind::UnexpectedCharacter(marker)));
         }
 
-        panic!("Cannot parse DMS string")
+        Ok(Self {
+            negative: parsed.negative,
+            degrees: parsed.whole as i16,
+            minutes: parsed.minutes,
+            seconds: parsed.seconds,
+        })
+    }
+}
+
+impl FromStr for DegMinSec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_string(s)
     }
 }
 
 impl Display for DegMinSec {
-    fn fmt(&self, f: &mut Formatter) -> Result {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         if self.negative {
             write!(
                 f,
@@ -171,34 +259,292 @@ impl Eq for DegMinSec {}
 pub type Declination = DegMinSec;
 pub type RightAscension = HoursMinSec;
 
-struct EqPosition {
+/// Equinox (and epoch) the coordinates of a position are referred to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Epoch {
+    /// Standard epoch J2000.0, JD 2451545.0.
+    J2000,
+    /// Standard epoch B1950.0, JD 2433282.4235.
+    B1950,
+    /// Julian epoch of the given year, e.g. `Julian(2024.5)` for J2024.5.
+    Julian(f64),
+    /// Besselian epoch of the given year, e.g. `Besselian(1875.0)` for B1875.0.
+    Besselian(f64),
+    /// Mean equinox of the given date.
+    OfDate(JulianDay),
+}
+
+impl Epoch {
+    /// Returns the instant of the epoch as a Julian Day (Meeus eq. 21.1 and p. 133).
+    #[must_use]
+    pub fn to_julian_day(&self) -> JulianDay {
+        match *self {
+            Self::J2000 => JulianDay::new(2_451_545.0),
+            Self::B1950 => JulianDay::new(2_433_282.423_5),
+            Self::Julian(year) => JulianDay::new(2_451_545.0 + (year - 2000.0) * 365.25),
+            Self::Besselian(year) => {
+                JulianDay::new(2_415_020.313_52 + (year - 1900.0) * 365.242_198_781)
+            }
+            Self::OfDate(jd) => jd,
+        }
+    }
+}
+
+/// Formats the epoch as `J2000`, `B1950`, a Julian or Besselian year such as
+/// `J2024.5` or `B1875.0`, or a Julian Day such as `JD2460000.5`.
+impl Display for Epoch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::J2000 => write!(f, "J2000"),
+            Self::B1950 => write!(f, "B1950"),
+            Self::Julian(year) => write!(f, "J{year:?}"),
+            Self::Besselian(year) => write!(f, "B{year:?}"),
+            Self::OfDate(jd) => write!(f, "JD{:?}", jd.get_value()),
+        }
+    }
+}
+
+impl FromStr for Epoch {
+    type Err = Error;
+
+    /// Parses the form produced by `Display`, returning [`Error::InvalidEpoch`]
+    /// if the string cannot be parsed.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |position, kind| Error::InvalidEpoch(ParseError::new(s, position, kind));
+        let start = s.len() - s.trim_start().len();
+        let epoch = s.trim();
+
+        let (number, make): (&str, fn(f64) -> Self) = match epoch {
+            "J2000" => return Ok(Self::J2000),
+            "B1950" => return Ok(Self::B1950),
+            "" => return Err(error(start, ParseErrorKind::Empty)),
+            _ => {
+                if let Some(jd) = epoch.strip_prefix("JD") {
+                    (jd, |jd| Self::OfDate(JulianDay::new(jd)))
+                } else if let Some(year) = epoch.strip_prefix('J') {
+                    (year, Self::Julian)
+                } else if let Some(year) = epoch.strip_prefix('B') {
+                    (year, Self::Besselian)
+                } else {
+                    let c = epoch.chars().next().unwrap_or_default();
+                    return Err(error(start, ParseErrorKind::UnexpectedCharacter(c)));
+                }
+            }
+        };
+
+        let position = start + epoch.len() - number.len();
+        match number.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(make(value)),
+            _ => Err(error(position, ParseErrorKind::InvalidNumber)),
+        }
+    }
+}
+
+/// Position in the equatorial coordinate system: right ascension and
+/// declination referred to the equinox of an [`Epoch`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EqPosition {
     ra: RightAscension,
     dec: Declination,
+    epoch: Epoch,
 }
 
 impl EqPosition {
-    pub fn from_string(ra: &str, dec: &str) -> Self {
+    /// Creates a position referred to J2000, see [`EqPosition::with_epoch`].
+    #[must_use]
+    pub fn new(ra: RightAscension, dec: Declination) -> Self {
         Self {
-            ra: RightAscension::from_string(ra),
-            dec: Declination::from_string(dec),
+            ra,
+            dec,
+            epoch: Epoch::J2000,
         }
     }
+
+    /// Creates a position referred to J2000 from right ascension and declination
+    /// in decimal degrees, the right ascension is reduced to the range 0-360.
+    #[must_use]
+    pub fn from_degrees(ra: f64, dec: f64) -> Self {
+        Self::new(
+            RightAscension::from_degrees(fit_degrees(ra)),
+            Declination::from_degrees(dec),
+        )
+    }
+
+    /// Creates a position referred to J2000 from right ascension and declination in radians.
+    #[must_use]
+    pub fn from_radians(ra: f64, dec: f64) -> Self {
+        Self::from_degrees(ra.to_degrees(), dec.to_degrees())
+    }
+
+    /// Creates a position referred to J2000 from the rectangular components of a
+    /// direction, e.g. the output of [`EqPosition::to_cartesian`].
+    #[must_use]
+    pub fn from_cartesian(v: [f64; 3]) -> Self {
+        let [x, y, z] = v;
+        Self::from_radians(y.atan2(x), z.atan2(x.hypot(y)))
+    }
+
+    /// Parses RA and declination strings, see [`HoursMinSec::from_string`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if either string cannot be parsed, see [`EqPosition::try_from_string`].
+    #[must_use]
+    pub fn from_string(ra: &str, dec: &str) -> Self {
+        Self::new(
+            RightAscension::from_string(ra),
+            Declination::from_string(dec),
+        )
+    }
+
+    /// Parses RA and declination strings, see [`HoursMinSec::try_from_string`]
+    /// and [`DegMinSec::try_from_string`].
+    pub fn try_from_string(ra: &str, dec: &str) -> Result<Self, Error> {
+        Ok(Self::new(ra.parse()?, dec.parse()?))
+    }
+
+    /// Returns the same position tagged with the given equinox.
+    ///
+    /// This doesn't transform the coordinates.
+    #[must_use]
+    pub fn with_epoch(self, epoch: Epoch) -> Self {
+        Self { epoch, ..self }
+    }
+
+    #[must_use]
+    pub fn ra(&self) -> RightAscension {
+        self.ra
+    }
+
+    #[must_use]
+    pub fn dec(&self) -> Declination {
+        self.dec
+    }
+
+    #[must_use]
+    pub fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    /// Right ascension in decimal degrees, in the range 0-360.
+    #[must_use]
+    pub fn ra_degrees(&self) -> f64 {
+        (f64::from(self.ra.hours)
+            + f64::from(self.ra.minutes) / 60_f64
+            + self.ra.seconds / 3600_f64)
+            * 15_f64
+    }
+
+    /// Declination in decimal degrees.
+    #[must_use]
+    pub fn dec_degrees(&self) -> f64 {
+        transform::dec_to_deg(&self.dec)
+    }
+
+    #[must_use]
+    pub fn ra_radians(&self) -> f64 {
+        self.ra_degrees().to_radians()
+    }
+
+    #[must_use]
+    pub fn dec_radians(&self) -> f64 {
+        self.dec_degrees().to_radians()
+    }
+
+    /// Returns the unit vector pointing at the position, with the x axis towards
+    /// the equinox and the z axis towards the celestial pole.
+    #[must_use]
+    pub fn to_cartesian(&self) -> [f64; 3] {
+        let (ra, dec) = (self.ra_radians(), self.dec_radians());
+        [dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin()]
+    }
 }
 
+/// Formats the position as `ra:... dec:...`, followed by ` epoch:...` (see
+/// [`Epoch`]'s `Display`) unless it is referred to J2000.
 impl Display for EqPosition {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "ra:{} dec:{}", self.ra, self.dec)
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "ra:{} dec:{}", self.ra, self.dec)?;
+        if self.epoch != Epoch::J2000 {
+            write!(f, " epoch:{}", self.epoch)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for EqPosition {
+    type Err = Error;
+
+    /// Parses a RA and a declination from one string, either in the form produced
+    /// by `Display` (`ra:... dec:...`), separated by a comma, or as two halves
+    /// with the same number of whitespace separated fields (`12 34 56 -28 09 44`).
+    ///
+    /// A trailing `epoch:...` tag, as written by `Display`, sets the epoch of the
+    /// position, which is J2000 otherwise.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((position, epoch)) = s.rsplit_once("epoch:") {
+            return Ok(position.parse::<Self>()?.with_epoch(epoch.parse()?));
+        }
+
+        if let Some((ra, dec)) = s.split_once("dec:") {
+            let ra = ra.trim().strip_prefix("ra:").unwrap_or(ra);
+            return Self::try_from_string(ra, dec);
+        }
+
+        if let Some((ra, dec)) = s.split_once(',') {
+            return Self::try_from_string(ra, dec);
+        }
+
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        if fields.is_empty() || fields.len() % 2 == 1 {
+            return Err(Error::InvalidPosition(ParseError::new(
+                s,
+                s.len(),
+                ParseErrorKind::UnexpectedEnd,
+            )));
+        }
+
+        let (ra, dec) = fields.split_at(fields.len() / 2);
+        Self::try_from_string(&ra.join(" "), &dec.join(" "))
     }
 }
 
+/// Geographic position of an observer, longitudes are positive east of Greenwich.
 pub struct LongLatPosition {
     pub long: DegMinSec,
     pub lat: DegMinSec,
 }
 
+impl LongLatPosition {
+    #[must_use]
+    pub fn new(long: DegMinSec, lat: DegMinSec) -> Self {
+        Self { long, lat }
+    }
+
+    /// Creates a position from longitude (east positive) and latitude in decimal degrees.
+    #[must_use]
+    pub fn from_degrees(long: f64, lat: f64) -> Self {
+        Self::new(DegMinSec::from_degrees(long), DegMinSec::from_degrees(lat))
+    }
+
+    /// Longitude in decimal degrees, east positive.
+    #[must_use]
+    pub fn long_degrees(&self) -> f64 {
+        transform::dec_to_deg(&self.long)
+    }
+
+    /// Latitude in decimal degrees.
+    #[must_use]
+    pub fn lat_degrees(&self) -> f64 {
+        transform::dec_to_deg(&self.lat)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::{Declination, EqPosition, RightAscension};
+    use crate::error::ParseErrorKind;
+    use crate::julian_day::JulianDay;
+    use crate::{Declination, Epoch, EqPosition, Error, RightAscension};
 
     #[test]
     fn test_dec_display() {
@@ -214,11 +560,56 @@ mod test {
 
     #[test]
     fn test_parse_ra_from_simple_str() {
-        let ra1 = RightAscension::from_string("28 45 78.81");
-        let ra2 = RightAscension::new(28, 45, 78.81);
+        let ra1 = RightAscension::from_string("18 45 58.81");
+        let ra2 = RightAscension::new(18, 45, 58.81);
         assert_eq!(ra1, ra2);
     }
 
+    #[test]
+    fn test_parse_ra_notations() {
+        let expected = RightAscension::new(12, 34, 56.7);
+        for s in ["12 34 56.7", "12:34:56.700", "12h34m56.7s", "12h 34m 56.7s"] {
+            assert_eq!(s.parse::<RightAscension>().unwrap(), expected, "{s}");
+        }
+        assert_eq!(
+            "6 5 4".parse::<RightAscension>().unwrap(),
+            RightAscension::new(6, 5, 4.0)
+        );
+        assert_eq!(
+            "12.5".parse::<RightAscension>().unwrap(),
+            RightAscension::new(12, 30, 0.0)
+        );
+        assert_eq!(
+            "187.5°".parse::<RightAscension>().unwrap(),
+            RightAscension::new(12, 30, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_parse_dms_notations() {
+        let expected = Declination::new(41, 16, 9.0);
+        for s in [
+            "41 16 09",
+            "+41:16:09",
+            "+41°16'09\"",
+            "41d16m9s",
+            "41° 16′ 09″",
+        ] {
+            assert_eq!(s.parse::<Declination>().unwrap(), expected, "{s}");
+        }
+
+        let dec = "-00 30 00".parse::<Declination>().unwrap();
+        assert!(dec.negative);
+        assert_eq!((dec.degrees, dec.minutes), (0, 30));
+
+        let dec = Declination::new(-28, 9, 44.08);
+        assert_eq!(format!("{}", dec).parse::<Declination>().unwrap(), dec);
+        assert_eq!(
+            "-28.5".parse::<Declination>().unwrap(),
+            Declination::new(-28, 30, 0.0)
+        );
+    }
+
     #[test]
     fn test_parse_dms_from_simple_str() {
         let dec1 = Declination::from_string("-28 09 44.08");
@@ -231,8 +622,141 @@ mod test {
         let ra = RightAscension::new(23, 44, 01.0);
         let dec = Declination::new(-28, 9, 44.08);
         let eq_pos = EqPosition::from_string("23 44 01", "-28 09 44.08");
-        assert_eq!(eq_pos.ra, ra);
-        assert_eq!(eq_pos.dec, dec);
+        assert_eq!(eq_pos.ra(), ra);
+        assert_eq!(eq_pos.dec(), dec);
+        assert_eq!(eq_pos.epoch(), Epoch::J2000);
         assert_eq!(format!("{}", eq_pos), "ra:23h 44m 1s dec:-28° 9' 44.08''");
     }
+
+    #[test]
+    fn test_eq_pos_constructors() {
+        let pos = EqPosition::from_degrees(356.0, -28.5);
+        assert_eq!(pos.ra(), RightAscension::new(23, 44, 0.0));
+        assert_eq!(pos.dec(), Declination::new(-28, 30, 0.0));
+        assert!((pos.ra_degrees() - 356.0).abs() < 1e-9);
+        assert!((pos.dec_degrees() + 28.5).abs() < 1e-9);
+
+        let from_rad = EqPosition::from_radians(pos.ra_radians(), pos.dec_radians());
+        assert_eq!(from_rad, pos);
+        assert_eq!(EqPosition::from_degrees(-4.0, -28.5), pos);
+
+        let from_cartesian = EqPosition::from_cartesian(pos.to_cartesian());
+        assert!((from_cartesian.ra_degrees() - 356.0).abs() < 1e-9);
+        assert!((from_cartesian.dec_degrees() + 28.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_eq_pos_epoch() {
+        let pos = EqPosition::from_degrees(10.0, 10.0).with_epoch(Epoch::B1950);
+        assert_eq!(pos.epoch(), Epoch::B1950);
+        assert_eq!(
+            Epoch::Julian(2000.0).to_julian_day(),
+            Epoch::J2000.to_julian_day()
+        );
+        assert!(
+            (Epoch::Besselian(1950.0).to_julian_day().get_value()
+                - Epoch::B1950.to_julian_day().get_value())
+            .abs()
+                < 1e-3
+        );
+        let jd = JulianDay::new(2_460_000.5);
+        assert_eq!(Epoch::OfDate(jd).to_julian_day(), jd);
+    }
+
+    #[test]
+    fn test_eq_pos_from_str() {
+        let expected = EqPosition::from_string("23 44 01", "-28 09 44.08");
+        for s in [
+            "23 44 01 -28 09 44.08",
+            "23:44:01 -28:09:44.08",
+            "23h44m01s, -28°09'44.08\"",
+            "ra:23h 44m 1s dec:-28° 9' 44.08''",
+        ] {
+            assert_eq!(s.parse::<EqPosition>().unwrap(), expected, "{s}");
+        }
+        let Err(Error::InvalidPosition(err)) = "23 44 01 -28 09".parse::<EqPosition>() else {
+            panic!("an odd number of fields should be rejected");
+        };
+        assert_eq!(
+            (err.kind, err.position),
+            (ParseErrorKind::UnexpectedEnd, 15)
+        );
+    }
+
+    #[test]
+    fn test_eq_pos_epoch_round_trip() {
+        let pos = EqPosition::from_string("23 44 01", "-28 09 44.08");
+        for epoch in [
+            Epoch::J2000,
+            Epoch::B1950,
+            Epoch::Julian(2000.0),
+            Epoch::Julian(2024.5),
+            Epoch::Besselian(1875.0),
+            Epoch::OfDate(JulianDay::new(2_460_000.5)),
+        ] {
+            let tagged = pos.with_epoch(epoch);
+            assert_eq!(tagged.to_string().parse::<EqPosition>().unwrap(), tagged);
+            assert_eq!(epoch.to_string().parse::<Epoch>().unwrap(), epoch);
+        }
+        assert_eq!(
+            pos.with_epoch(Epoch::B1950).to_string(),
+            "ra:23h 44m 1s dec:-28° 9' 44.08'' epoch:B1950"
+        );
+        assert_eq!(
+            "23 44 01, -28 09 44.08 epoch:JD2460000.5"
+                .parse::<EqPosition>()
+                .unwrap()
+                .epoch(),
+            Epoch::OfDate(JulianDay::new(2_460_000.5))
+        );
+
+        let Err(Error::InvalidEpoch(err)) = " J20x0".parse::<Epoch>() else {
+            panic!("bad year should be rejected");
+        };
+        assert_eq!(err.kind, ParseErrorKind::InvalidNumber);
+        assert_eq!(err.position, 2);
+        assert!("X2000".parse::<Epoch>().is_err());
+        assert!("ra:23h 44m 1s dec:-28° 9' 44.08'' epoch:"
+            .parse::<EqPosition>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_bad_strings_returns_error() {
+        let Err(Error::InvalidRightAscension(err)) = "24:45:18".parse::<RightAscension>() else {
+            panic!("24h should be rejected");
+        };
+        assert_eq!(err.kind, ParseErrorKind::OutOfRange);
+        assert_eq!(err.position, 0);
+
+        let Err(Error::InvalidRightAscension(err)) = "-01 00 00".parse::<RightAscension>() else {
+            panic!("negative RA should be rejected");
+        };
+        assert_eq!(err.kind, ParseErrorKind::NegativeRightAscension);
+        assert_eq!(err.position, 0);
+
+        let Err(Error::InvalidRightAscension(err)) = "+24h 00m".parse::<RightAscension>() else {
+            panic!("24h should be rejected");
+        };
+        assert_eq!((err.kind, err.position), (ParseErrorKind::OutOfRange, 1));
+
+        let Err(Error::InvalidDegMinSec(err)) = "-12h30m".parse::<Declination>() else {
+            panic!("hours should be rejected for a declination");
+        };
+        assert_eq!(
+            (err.kind, err.position),
+            (ParseErrorKind::UnexpectedCharacter('h'), 3)
+        );
+        let Err(Error::InvalidDegMinSec(err)) = " 361 00 00".parse::<Declination>() else {
+            panic!("361° should be rejected");
+        };
+        assert_eq!((err.kind, err.position), (ParseErrorKind::OutOfRange, 1));
+
+        let Err(Error::InvalidDegMinSec(err)) = "north".parse::<Declination>() else {
+            panic!("text should be rejected");
+        };
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedCharacter('n'));
+        assert_eq!(err.position, 0);
+        assert!(EqPosition::try_from_string("23 44 01", "-28 9 64").is_err());
+    }
 }
@@ -0,0 +1,216 @@
+//! Civil dates in a local time zone.
+//!
+//! A [`LocalDate`] is a calendar date and time of day together with the
+//! [`UtcOffset`] it is expressed in, either fixed or looked up in a
+//! [`TimeZone`].
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::date::Date;
+use crate::julian_day::JulianDay;
+use crate::parse::parse_iso_date;
+use crate::timezone::TimeZone;
+use crate::Error;
+
+/// Difference between a local time and UTC, positive east of Greenwich.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UtcOffset {
+    seconds: i32,
+}
+
+impl UtcOffset {
+    /// The offset of UTC itself.
+    pub const UTC: Self = Self { seconds: 0 };
+
+    #[must_use]
+    pub fn from_seconds(seconds: i32) -> Self {
+        Self { seconds }
+    }
+
+    /// Creates an offset from hours and minutes, the sign of `hours` applies to
+    /// both (e.g. `from_hours_minutes(-3, 30)` is UTC−03:30).
+    #[must_use]
+    pub fn from_hours_minutes(hours: i8, minutes: u8) -> Self {
+        let seconds = i32::from(hours.unsigned_abs()) * 3600 + i32::from(minutes) * 60;
+        Self::from_seconds(if hours < 0 { -seconds } else { seconds })
+    }
+
+    #[must_use]
+    pub fn seconds(&self) -> i32 {
+        self.seconds
+    }
+
+    /// The offset as a fraction of day.
+    #[must_use]
+    pub fn days(&self) -> f64 {
+        f64::from(self.seconds) / 86400_f64
+    }
+}
+
+impl Display for UtcOffset {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let sign = if self.seconds < 0 { '-' } else { '+' };
+        let seconds = self.seconds.unsigned_abs();
+        write!(f, "{sign}{:02}:{:02}", seconds / 3600, seconds % 3600 / 60)?;
+        let remainder = seconds % 60;
+        if remainder > 0 {
+            write!(f, ":{remainder:02}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A calendar date and time of day in a local time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalDate {
+    /// Local calendar date and time of day.
+    pub date: Date,
+    /// Offset of the local time from UTC.
+    pub offset: UtcOffset,
+}
+
+impl LocalDate {
+    #[must_use]
+    pub fn new(date: Date, offset: UtcOffset) -> Self {
+        Self { date, offset }
+    }
+
+    /// Returns the local date of a Julian Day (UTC) for a fixed offset.
+    #[must_use]
+    pub fn from_julian_day(jd: &JulianDay, offset: UtcOffset) -> Self {
+        Self::new(Date::from_julian_day(&(*jd + offset.days())), offset)
+    }
+
+    /// Returns the local date of a Julian Day (UTC) in a time zone.
+    #[must_use]
+    pub fn from_julian_day_in_zone(jd: &JulianDay, zone: &TimeZone) -> Self {
+        Self::from_julian_day(jd, zone.offset_at(jd))
+    }
+
+    /// Returns the local date in a time zone of a local date and time without
+    /// offset, see [`TimeZone::offset_at_local`].
+    #[must_use]
+    pub fn from_date_in_zone(date: Date, zone: &TimeZone) -> Self {
+        Self::new(date, zone.offset_at_local(&date.to_julian_day()))
+    }
+
+    /// Parses an ISO 8601 / RFC 3339 date keeping its offset, a date without
+    /// offset is read as UTC, see [`Date::try_from_string`].
+    pub fn try_from_string(s: &str) -> Result<Self, Error> {
+        let parsed = parse_iso_date(s).map_err(Error::InvalidDate)?;
+        Ok(Self::new(
+            Date::from_full_date(
+                parsed.year,
+                parsed.month,
+                f64::from(parsed.day),
+                parsed.hours,
+                parsed.minutes,
+                parsed.seconds,
+            ),
+            UtcOffset::from_seconds(parsed.offset.unwrap_or(0)),
+        ))
+    }
+
+    /// Returns the Julian Day (UTC) of the local date (libnova's `ln_get_julian_local_date`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the month of the date is invalid, see [`LocalDate::try_to_julian_day`].
+    #[must_use]
+    pub fn to_julian_day(&self) -> JulianDay {
+        self.try_to_julian_day().expect("Error")
+    }
+
+    /// Fallible version of [`LocalDate::to_julian_day`].
+    pub fn try_to_julian_day(&self) -> Result<JulianDay, Error> {
+        Ok(self.date.try_to_julian_day()? - self.offset.days())
+    }
+
+    /// Returns the same instant as a UTC date.
+    #[must_use]
+    pub fn to_utc(&self) -> Date {
+        Date::from_julian_day(&self.to_julian_day())
+    }
+}
+
+impl FromStr for LocalDate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_string(s)
+    }
+}
+
+/// Formats the date as RFC 3339 with microseconds, e.g. `2000-01-01T20:00:00.000000+08:00`.
+impl Display for LocalDate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.date.write_iso(f, 6)?;
+        write!(f, "{}", self.offset)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn j2000() -> JulianDay {
+        JulianDay::new(2_451_545.0)
+    }
+
+    #[test]
+    fn test_utc_offset() {
+        assert_eq!(UtcOffset::from_hours_minutes(5, 30).seconds(), 19_800);
+        assert_eq!(UtcOffset::from_hours_minutes(-3, 30).seconds(), -12_600);
+        assert_eq!(UtcOffset::from_hours_minutes(-3, 30).to_string(), "-03:30");
+        assert_eq!(UtcOffset::UTC.to_string(), "+00:00");
+        assert_eq!(UtcOffset::from_seconds(4_772).to_string(), "+01:19:32");
+    }
+
+    #[test]
+    fn test_local_date_to_julian_day() {
+        // 2000-01-01 20:00 at UTC+08:00 is 12:00 UTC, J2000.0
+        let local = LocalDate::new(
+            Date::from_full_date(2000, 1, 1.0, 20, 0, 0.0),
+            UtcOffset::from_hours_minutes(8, 0),
+        );
+        assert!(local.to_julian_day().days_since(&j2000()).abs() < 1e-12);
+        assert_eq!(
+            local.to_utc(),
+            Date::from_full_date(2000, 1, 1.0, 12, 0, 0.0)
+        );
+        assert_eq!(local.to_string(), "2000-01-01T20:00:00.000000+08:00");
+        assert_eq!(local.to_string().parse::<LocalDate>().unwrap(), local);
+        assert_eq!(
+            "2000-01-01T20:00:00".parse::<LocalDate>().unwrap().offset,
+            UtcOffset::UTC
+        );
+    }
+
+    #[test]
+    fn test_local_date_from_julian_day() {
+        // J2000.0 seen from UTC−05:00 is the morning of January 1st.
+        let offset = UtcOffset::from_hours_minutes(-5, 0);
+        let local = LocalDate::from_julian_day(&JulianDay::new(2_451_545.0), offset);
+        assert_eq!(local.date, Date::from_full_date(2000, 1, 1.0, 7, 0, 0.0));
+
+        // and the previous evening from UTC−14:00
+        let local = LocalDate::from_julian_day(
+            &JulianDay::new(2_451_545.0),
+            UtcOffset::from_hours_minutes(-14, 0),
+        );
+        assert_eq!(local.date, Date::from_full_date(1999, 12, 31.0, 22, 0, 0.0));
+        assert!(local.to_julian_day().days_since(&j2000()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_local_date_in_zone() {
+        let zone = TimeZone::fixed(UtcOffset::from_hours_minutes(1, 0));
+        let local = LocalDate::from_julian_day_in_zone(&j2000(), &zone);
+        assert_eq!(local.date, Date::from_full_date(2000, 1, 1.0, 13, 0, 0.0));
+
+        let local = LocalDate::from_date_in_zone(local.date, &zone);
+        assert_eq!(local.offset.seconds(), 3600);
+        assert!(local.to_julian_day().days_since(&j2000()).abs() < 1e-12);
+    }
+}
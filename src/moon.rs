@@ -3,8 +3,10 @@
 //! Implements the algorithm from Jean Meeus, *Astronomical Algorithms*, 2nd ed.,
 //! Chapter 47 "Position of the Moon".
 
+use crate::ecliptic::{ecl_to_equ, EclipticPosition};
 use crate::fit_degrees;
 use crate::julian_day::JulianDay;
+use crate::nutation::get_mean_obliquity;
 
 /// Geocentric position of the Moon.
 #[derive(Debug)]
@@ -154,7 +156,8 @@ static TABLE_47B: &[(i8, i8, i8, i8, i64)] = &[
     (2, -2, 0, 1, 107),
 ];
 
-/// Compute the geocentric position of the Moon for the given Julian Day.
+/// Compute the geocentric position of the Moon for the given Julian Ephemeris
+/// Day (TD), see [`JulianDay::to_jde`] to convert a Julian Day in UT.
 ///
 /// Returns a [`MoonPosition`] containing the geocentric ecliptic longitude and
 /// latitude (in degrees), the Earth–Moon distance (in km), and the equatorial
@@ -164,8 +167,7 @@ static TABLE_47B: &[(i8, i8, i8, i8, i64)] = &[
 /// equations 47.1–47.5 and Tables 47.A–47.B.
 #[must_use]
 pub fn get_moon_position(jd: &JulianDay) -> MoonPosition {
-    let pi = std::f64::consts::PI;
-    let to_rad = pi / 180.0;
+    let to_rad = std::f64::consts::PI / 180.0;
 
     // Julian centuries from J2000.0 (Meeus eq. 47.1)
     let t = (jd.get_value() - 2_451_545.0) / 36_525.0;
@@ -277,27 +279,11 @@ pub fn get_moon_position(jd: &JulianDay) -> MoonPosition {
     // Distance Earth–Moon Δ (km)
     let delta = 385_000.56 + sum_r / 1_000.0;
 
-    // Convert geocentric ecliptic to equatorial coordinates.
-    //
-    // Mean obliquity of the ecliptic ε (Meeus Ch. 22, eq. 22.2, low-precision form):
-    //   ε = 23°26'21.448" − 46.8150"·T − 0.00059"·T² + 0.001813"·T³
-    let epsilon = (23.0 + 26.0 / 60.0 + 21.448 / 3_600.0
-        - (46.815_0 / 3_600.0) * t
-        - (0.000_59 / 3_600.0) * t2
-        + (0.001_813 / 3_600.0) * t3)
-        * to_rad;
-
-    let lambda_rad = lambda * to_rad;
-    let beta_rad = beta * to_rad;
-
-    // Meeus eq. 13.3 and 13.4 (ecliptic → equatorial)
-    let ra_rad =
-        (lambda_rad.sin() * epsilon.cos() - beta_rad.tan() * epsilon.sin()).atan2(lambda_rad.cos());
-    let dec_rad =
-        (beta_rad.sin() * epsilon.cos() + beta_rad.cos() * epsilon.sin() * lambda_rad.sin()).asin();
-
-    let ra = fit_degrees(ra_rad * 180.0 / pi);
-    let dec = dec_rad * 180.0 / pi;
+    // Convert geocentric ecliptic to equatorial coordinates using the mean
+    // obliquity of the ecliptic (Meeus eq. 22.2).
+    let equatorial = ecl_to_equ(&EclipticPosition::new(lambda, beta), get_mean_obliquity(jd));
+    let ra = equatorial.ra_degrees();
+    let dec = equatorial.dec_degrees();
 
     MoonPosition {
         longitude: lambda,
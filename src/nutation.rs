@@ -3,8 +3,12 @@
 //! Implements the IAU 1980 theory of nutation (63-term series) and returns:
 //! - Δψ: nutation in longitude (arcseconds)
 //! - Δε: nutation in obliquity (arcseconds)
+//!
+//! together with the mean and true obliquity of the ecliptic.
 
 use crate::julian_day::JulianDay;
+use crate::precession::{apply_rotation, RotationMatrix};
+use crate::{Epoch, EqPosition};
 
 /// Nutation in longitude and obliquity for a given instant.
 #[derive(Debug, PartialEq)]
@@ -92,6 +96,7 @@ static TABLE_22A: &[(i8, i8, i8, i8, i8, i32, f64, i32, f64)] = &[
 /// *Astronomical Algorithms*, 2nd ed., Chapter 22, Table 22.A, pp. 145–146.
 ///
 /// Both components of the returned [`Nutation`] are in **arcseconds**.
+/// `jd` is a Julian Ephemeris Day (TD), see [`JulianDay::to_jde`].
 #[must_use]
 pub fn get_nutation(jd: &JulianDay) -> Nutation {
     // Julian centuries from J2000.0 — eq. 22.1
@@ -132,6 +137,60 @@ pub fn get_nutation(jd: &JulianDay) -> Nutation {
     }
 }
 
+/// Mean obliquity of the ecliptic ε0 in degrees for the given Julian Ephemeris Day.
+///
+/// Meeus eq. 22.2 (IAU): ε0 = 23°26'21.448" − 46.8150"·T − 0.00059"·T² + 0.001813"·T³
+#[must_use]
+pub fn get_mean_obliquity(jd: &JulianDay) -> f64 {
+    let t = (jd.get_value() - 2_451_545.0) / 36_525.0;
+
+    23.0 + 26.0 / 60.0 + 21.448 / 3_600.0 - (46.815_0 / 3_600.0) * t - (0.000_59 / 3_600.0) * t * t
+        + (0.001_813 / 3_600.0) * t * t * t
+}
+
+/// True obliquity of the ecliptic ε = ε0 + Δε in degrees for the given Julian Ephemeris Day.
+#[must_use]
+pub fn get_true_obliquity(jd: &JulianDay) -> f64 {
+    get_mean_obliquity(jd) + get_nutation(jd).delta_eps / 3_600.0
+}
+
+/// Returns the nutation matrix rotating rectangular equatorial coordinates from
+/// the mean to the true equator and equinox of date.
+#[must_use]
+pub fn get_nutation_matrix(jd: &JulianDay) -> RotationMatrix {
+    let nutation = get_nutation(jd);
+    let (sin_eps, cos_eps) = get_mean_obliquity(jd).to_radians().sin_cos();
+    let (sin_true, cos_true) = (get_mean_obliquity(jd) + nutation.delta_eps / 3_600.0)
+        .to_radians()
+        .sin_cos();
+    let (sin_psi, cos_psi) = (nutation.delta_psi / 3_600.0).to_radians().sin_cos();
+
+    // R1(−ε) · R3(−Δψ) · R1(ε0)
+    [
+        [cos_psi, -sin_psi * cos_eps, -sin_psi * sin_eps],
+        [
+            sin_psi * cos_true,
+            cos_psi * cos_true * cos_eps + sin_true * sin_eps,
+            cos_psi * cos_true * sin_eps - sin_true * cos_eps,
+        ],
+        [
+            sin_psi * sin_true,
+            cos_psi * sin_true * cos_eps - cos_true * sin_eps,
+            cos_psi * sin_true * sin_eps + cos_true * cos_eps,
+        ],
+    ]
+}
+
+/// Corrects a position referred to the mean equinox of date for nutation,
+/// returning it referred to the true equinox of date.
+///
+/// Rigorous matrix form of Meeus eq. 23.1, usable close to the celestial poles.
+#[must_use]
+pub fn get_equ_nut(pos: &EqPosition, jd: &JulianDay) -> EqPosition {
+    let v = apply_rotation(&get_nutation_matrix(jd), pos.to_cartesian());
+    EqPosition::from_cartesian(v).with_epoch(Epoch::OfDate(*jd))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -158,4 +217,37 @@ mod test {
             nut.delta_eps
         );
     }
+
+    #[test]
+    fn test_obliquity_1987_apr_10() {
+        // Meeus, Astronomical Algorithms, 2nd ed., Example 22.a, p. 148
+        // Expected: ε0 = 23°26'27.407", ε = 23°26'36.850"
+        let jd = JulianDay::new(2_446_895.5);
+        let mean = get_mean_obliquity(&jd);
+        let true_obliquity = get_true_obliquity(&jd);
+
+        assert!(
+            (mean - (23.0 + 26.0 / 60.0 + 27.407 / 3_600.0)).abs() * 3_600.0 < 0.001,
+            "mean obliquity = {mean}"
+        );
+        assert!(
+            (true_obliquity - (23.0 + 26.0 / 60.0 + 36.850 / 3_600.0)).abs() * 3_600.0 < 0.005,
+            "true obliquity = {true_obliquity}"
+        );
+    }
+
+    #[test]
+    fn test_equ_nutation_meeus_example_23a() {
+        // Meeus, Astronomical Algorithms, 2nd ed., Example 23.a, p. 156
+        // θ Persei, mean place of date α = 41.5472°, δ = 49.3485°
+        // Expected: Δα1 = +15.843", Δδ1 = +6.218"
+        let jd = JulianDay::new(2_462_088.69);
+        let pos = EqPosition::from_degrees(41.547_2, 49.348_5);
+        let res = get_equ_nut(&pos, &jd);
+
+        let d_alpha = (res.ra_degrees() - pos.ra_degrees()) * 3_600.0;
+        let d_delta = (res.dec_degrees() - pos.dec_degrees()) * 3_600.0;
+        assert!((d_alpha - 15.843).abs() < 0.01, "Δα = {d_alpha}");
+        assert!((d_delta - 6.218).abs() < 0.01, "Δδ = {d_delta}");
+    }
 }
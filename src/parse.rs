@@ -0,0 +1,575 @@
+//! Parsers for sexagesimal and decimal angles and for calendar dates.
+//!
+//! Angles are accepted in the notations commonly found in catalogues, FITS
+//! headers and mount controller replies, e.g. `12 34 56.7`, `12:34:56.789`,
+//! `12h34m56.7s`, `+41°16'09"`, `-00 30 00`, `12 34.5` or plain decimal `187.25`.
+//!
+//! Dates are accepted in the ISO 8601 extended format, e.g. `2000-06-17`,
+//! `2000-06-17T08:34:57.25Z`, `-0044-03-15T12:00+01:00`, and in the old FITS
+//! `DD/MM/YY` form.
+
+use crate::date::Calendar;
+use crate::error::{ParseError, ParseErrorKind};
+
+/// Unit given explicitly by the marker following the first field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Unit {
+    Hours,
+    Degrees,
+}
+
+/// A parsed angle split into its sexagesimal fields.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Sexagesimal {
+    pub negative: bool,
+    pub whole: u16,
+    pub minutes: u8,
+    pub seconds: f64,
+    pub unit: Option<Unit>,
+    /// Byte offsets in the input of the sign (or of the first field if there
+    /// is none), of the first field and of the unit marker, if any.
+    pub sign_position: usize,
+    pub whole_position: usize,
+    pub unit_position: Option<usize>,
+}
+
+impl Sexagesimal {
+    fn from_decimal(negative: bool, value: f64, unit: Option<Unit>) -> Self {
+        let whole = value.trunc();
+        let minutes = ((value - whole) * 60_f64).trunc();
+        let seconds = ((value - whole) * 60_f64 - minutes) * 60_f64;
+
+        Self {
+            negative,
+            whole: whole as u16,
+            minutes: minutes as u8,
+            seconds,
+            unit,
+            sign_position: 0,
+            whole_position: 0,
+            unit_position: None,
+        }
+    }
+
+    /// Total value in units of the first field.
+    pub fn value(&self) -> f64 {
+        f64::from(self.whole) + f64::from(self.minutes) / 60_f64 + self.seconds / 3600_f64
+    }
+}
+
+struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn eat(&mut self, chars: &[char]) -> bool {
+        match self.peek() {
+            Some(c) if chars.contains(&c) => {
+                self.bump();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(self.input, self.pos, kind)
+    }
+
+    fn unexpected(&self) -> ParseError {
+        match self.peek() {
+            Some(c) => self.error(ParseErrorKind::UnexpectedCharacter(c)),
+            None => self.error(ParseErrorKind::UnexpectedEnd),
+        }
+    }
+
+    /// Reads an unsigned number made of digits and an optional decimal part,
+    /// returning its text and whether it has a decimal part.
+    fn number(&mut self) -> Result<(&'a str, bool), ParseError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+
+        if self.pos == start {
+            return Err(self.unexpected());
+        }
+
+        let has_fraction = self.eat(&['.']);
+        if has_fraction {
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.bump();
+            }
+        }
+
+        Ok((&self.input[start..self.pos], has_fraction))
+    }
+
+    /// Reads between `min` and `max` digits as an integer, `OutOfRange` is
+    /// reported if it doesn't fit `range`.
+    fn integer(
+        &mut self,
+        min: usize,
+        max: usize,
+        range: std::ops::RangeInclusive<i32>,
+    ) -> Result<i32, ParseError> {
+        let start = self.pos;
+        while self.pos - start < max && self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+
+        if self.pos - start < min {
+            return Err(self.unexpected());
+        }
+
+        match self.input[start..self.pos].parse() {
+            Ok(value) if range.contains(&value) => Ok(value),
+            _ => Err(ParseError::new(
+                self.input,
+                start,
+                ParseErrorKind::OutOfRange,
+            )),
+        }
+    }
+
+    fn expect(&mut self, chars: &[char]) -> Result<(), ParseError> {
+        if self.eat(chars) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    /// Consumes the marker closing the field at `index`, e.g. `h`, `'` or `″`.
+    fn marker(&mut self, index: usize) -> Option<Option<Unit>> {
+        match index {
+            0 if self.eat(&['h', 'H']) => Some(Some(Unit::Hours)),
+            0 if self.eat(&['d', 'D', '°', 'º']) => Some(Some(Unit::Degrees)),
+            1 if self.eat(&['m', 'M', '\'', '′']) => Some(None),
+            2 if self.eat(&['s', 'S', '"', '″']) => Some(None),
+            2 if self.input[self.pos..].starts_with("''") => {
+                self.pos += 2;
+                Some(None)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses an angle made of up to three sexagesimal fields, of which only the
+/// last one may have a decimal part.
+pub(crate) fn parse_sexagesimal(input: &str) -> Result<Sexagesimal, ParseError> {
+    let mut cursor = Cursor { input, pos: 0 };
+    cursor.skip_spaces();
+
+    if cursor.peek().is_none() {
+        return Err(cursor.error(ParseErrorKind::Empty));
+    }
+
+    let sign_position = cursor.pos;
+    let negative = cursor.eat(&['-', '−']);
+    let signed = negative || cursor.eat(&['+']);
+
+    let mut fields: Vec<(&str, usize)> = Vec::with_capacity(3);
+    let mut unit = None;
+    let mut unit_position = None;
+
+    loop {
+        let index = fields.len();
+        let start = cursor.pos;
+        let (text, fraction) = cursor.number()?;
+        fields.push((text, start));
+        let last = fraction || index == 2;
+
+        let marker_position = cursor.pos;
+        if let Some(marker_unit) = cursor.marker(index) {
+            if index == 0 {
+                unit = marker_unit;
+                unit_position = Some(marker_position);
+            }
+            cursor.skip_spaces();
+            if cursor.peek().is_none() {
+                break;
+            }
+            if last {
+                return Err(cursor.unexpected());
+            }
+        } else if !last && cursor.eat(&[':']) {
+            continue;
+        } else {
+            cursor.skip_spaces();
+            if cursor.peek().is_none() {
+                break;
+            }
+            if last || !cursor.peek().is_some_and(|c| c.is_ascii_digit()) {
+                return Err(cursor.unexpected());
+            }
+        }
+    }
+
+    let out_of_range = |pos| ParseError::new(input, pos, ParseErrorKind::OutOfRange);
+    let invalid_number = |pos| ParseError::new(input, pos, ParseErrorKind::InvalidNumber);
+
+    let (whole_text, whole_pos) = fields[0];
+    let sign_position = if signed { sign_position } else { whole_pos };
+    if fields.len() == 1 {
+        let value = whole_text
+            .parse::<f64>()
+            .map_err(|_| invalid_number(whole_pos))?;
+        if value >= f64::from(u16::MAX) {
+            return Err(out_of_range(whole_pos));
+        }
+        return Ok(Sexagesimal {
+            sign_position,
+            whole_position: whole_pos,
+            unit_position,
+            ..Sexagesimal::from_decimal(negative, value, unit)
+        });
+    }
+
+    let whole = whole_text
+        .parse::<u16>()
+        .map_err(|_| out_of_range(whole_pos))?;
+
+    let (minutes_text, minutes_pos) = fields[1];
+    let minutes = minutes_text
+        .parse::<f64>()
+        .map_err(|_| invalid_number(minutes_pos))?;
+    if minutes >= 60_f64 {
+        return Err(out_of_range(minutes_pos));
+    }
+
+    let seconds = if let Some(&(seconds_text, seconds_pos)) = fields.get(2) {
+        let seconds = seconds_text
+            .parse::<f64>()
+            .map_err(|_| invalid_number(seconds_pos))?;
+        if seconds >= 60_f64 {
+            return Err(out_of_range(seconds_pos));
+        }
+        seconds
+    } else {
+        minutes.fract() * 60_f64
+    };
+
+    Ok(Sexagesimal {
+        negative,
+        whole,
+        minutes: minutes.trunc() as u8,
+        seconds,
+        unit,
+        sign_position,
+        whole_position: whole_pos,
+        unit_position,
+    })
+}
+
+/// Fields of a parsed date, `offset` is the UTC offset in seconds if one was given.
+#[derive(Debug, PartialEq)]
+pub(crate) struct IsoDate {
+    pub year: i16,
+    pub month: u8,
+    pub day: u8,
+    pub hours: u8,
+    pub minutes: u8,
+    pub seconds: f64,
+    pub offset: Option<i32>,
+}
+
+/// Parses a date of the [`Calendar::Gregorian`] calendar in the ISO 8601 extended format, `[±Y]YYYY-MM-DD` optionally
+/// followed by `Thh:mm[:ss[.s]]` and `Z` or a `±hh[:mm]` offset, or an old FITS
+/// `DD/MM/YY` date of the 20th century.
+///
+/// Years of more than 4 digits or before year 0 need the explicit sign of the
+/// expanded representation, e.g. `-0044` (astronomical year numbering). Like
+/// RFC 3339, a space may separate the date and the time, and seconds up to
+/// 60.999 are accepted for leap seconds.
+pub(crate) fn parse_iso_date(input: &str) -> Result<IsoDate, ParseError> {
+    let mut cursor = Cursor { input, pos: 0 };
+
+    if input.trim().is_empty() {
+        return Err(cursor.error(ParseErrorKind::Empty));
+    }
+
+    if input.as_bytes().get(2) == Some(&b'/') {
+        return parse_fits_legacy_date(cursor);
+    }
+
+    let year_sign = match cursor.peek() {
+        Some('-' | '−') => -1,
+        Some('+') => 1,
+        _ => 0,
+    };
+    if year_sign != 0 {
+        cursor.bump();
+    }
+    let year_pos = cursor.pos;
+    let year = if year_sign == 0 {
+        cursor.integer(4, 4, 0..=9999)?
+    } else {
+        year_sign * cursor.integer(4, 6, 0..=32767)?
+    };
+    if year < i32::from(i16::MIN) {
+        return Err(ParseError::new(input, year_pos, ParseErrorKind::OutOfRange));
+    }
+
+    cursor.expect(&['-'])?;
+    let month = cursor.integer(2, 2, 1..=12)?;
+    cursor.expect(&['-'])?;
+    let day_pos = cursor.pos;
+    let day = cursor.integer(2, 2, 1..=31)?;
+    if !Calendar::default().is_valid_date(year as i16, month as u8, f64::from(day)) {
+        return Err(ParseError::new(input, day_pos, ParseErrorKind::OutOfRange));
+    }
+
+    let mut date = IsoDate {
+        year: year as i16,
+        month: month as u8,
+        day: day as u8,
+        hours: 0,
+        minutes: 0,
+        seconds: 0.0,
+        offset: None,
+    };
+
+    if cursor.peek().is_none() {
+        return Ok(date);
+    }
+
+    cursor.expect(&['T', 't', ' '])?;
+    date.hours = cursor.integer(2, 2, 0..=23)? as u8;
+    cursor.expect(&[':'])?;
+    date.minutes = cursor.integer(2, 2, 0..=59)? as u8;
+
+    if cursor.eat(&[':']) {
+        let seconds_pos = cursor.pos;
+        let seconds = f64::from(cursor.integer(2, 2, 0..=60)?);
+        let fraction = if cursor.eat(&['.', ',']) {
+            let start = cursor.pos;
+            while cursor.peek().is_some_and(|c| c.is_ascii_digit()) {
+                cursor.bump();
+            }
+            if cursor.pos == start {
+                return Err(cursor.unexpected());
+            }
+            format!("0.{}", &input[start..cursor.pos])
+                .parse::<f64>()
+                .map_err(|_| ParseError::new(input, start, ParseErrorKind::InvalidNumber))?
+        } else {
+            0.0
+        };
+        date.seconds = seconds + fraction;
+        if date.seconds >= 61.0 {
+            return Err(ParseError::new(
+                input,
+                seconds_pos,
+                ParseErrorKind::OutOfRange,
+            ));
+        }
+    }
+
+    match cursor.peek() {
+        None => {}
+        Some('Z' | 'z') => {
+            cursor.bump();
+            date.offset = Some(0);
+        }
+        Some(sign @ ('+' | '-' | '−')) => {
+            cursor.bump();
+            let hours = cursor.integer(2, 2, 0..=23)?;
+            let minutes = if cursor.eat(&[':']) {
+                cursor.integer(2, 2, 0..=59)?
+            } else {
+                0
+            };
+            let seconds = hours * 3600 + minutes * 60;
+            date.offset = Some(if sign == '+' { seconds } else { -seconds });
+        }
+        Some(_) => return Err(cursor.unexpected()),
+    }
+
+    if cursor.peek().is_some() {
+        return Err(cursor.unexpected());
+    }
+
+    Ok(date)
+}
+
+/// Parses the `DD/MM/YY` form used by FITS `DATE-OBS` keywords before 1999.
+fn parse_fits_legacy_date(mut cursor: Cursor) -> Result<IsoDate, ParseError> {
+    let day_pos = cursor.pos;
+    let day = cursor.integer(2, 2, 1..=31)?;
+    cursor.expect(&['/'])?;
+    let month = cursor.integer(2, 2, 1..=12)?;
+    cursor.expect(&['/'])?;
+    let year = 1900 + cursor.integer(2, 2, 0..=99)? as i16;
+
+    if cursor.peek().is_some() {
+        return Err(cursor.unexpected());
+    }
+    if !Calendar::default().is_valid_date(year, month as u8, f64::from(day)) {
+        return Err(ParseError::new(
+            cursor.input,
+            day_pos,
+            ParseErrorKind::OutOfRange,
+        ));
+    }
+
+    Ok(IsoDate {
+        year,
+        month: month as u8,
+        day: day as u8,
+        hours: 0,
+        minutes: 0,
+        seconds: 0.0,
+        offset: None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::ParseErrorKind;
+    use crate::parse::{parse_iso_date, parse_sexagesimal, Sexagesimal, Unit};
+
+    #[test]
+    fn test_parse_all_separators() {
+        let expected = Sexagesimal {
+            negative: false,
+            whole: 12,
+            minutes: 34,
+            seconds: 56.789,
+            unit: None,
+            sign_position: 0,
+            whole_position: 0,
+            unit_position: None,
+        };
+        assert_eq!(parse_sexagesimal("12 34 56.789").unwrap(), expected);
+        assert_eq!(parse_sexagesimal("12:34:56.789").unwrap(), expected);
+        assert_eq!(
+            parse_sexagesimal("  12  34  56.789 ").unwrap(),
+            Sexagesimal {
+                sign_position: 2,
+                whole_position: 2,
+                ..expected
+            }
+        );
+
+        let hms = parse_sexagesimal("12h34m56.789s").unwrap();
+        assert_eq!(hms.unit, Some(Unit::Hours));
+        assert_eq!(hms.seconds, 56.789);
+
+        let dms = parse_sexagesimal("+41°16'09\"").unwrap();
+        assert_eq!((dms.whole, dms.minutes, dms.seconds), (41, 16, 9.0));
+        assert_eq!(dms.unit, Some(Unit::Degrees));
+        assert!(parse_sexagesimal("41° 16′ 09″").is_ok());
+        assert!(parse_sexagesimal("-41d 16m 09.5s").unwrap().negative);
+        assert!(parse_sexagesimal("-28° 9' 44.08''").unwrap().negative);
+
+        let positions = parse_sexagesimal(" -12h34m").unwrap();
+        assert_eq!(
+            (
+                positions.sign_position,
+                positions.whole_position,
+                positions.unit_position
+            ),
+            (1, 2, Some(4))
+        );
+    }
+
+    #[test]
+    fn test_parse_partial_and_decimal_fields() {
+        let neg_zero = parse_sexagesimal("-00 30 00").unwrap();
+        assert!(neg_zero.negative);
+        assert_eq!((neg_zero.whole, neg_zero.minutes), (0, 30));
+
+        let single_digits = parse_sexagesimal("1 2 3").unwrap();
+        assert_eq!(
+            (
+                single_digits.whole,
+                single_digits.minutes,
+                single_digits.seconds
+            ),
+            (1, 2, 3.0)
+        );
+
+        let decimal_minutes = parse_sexagesimal("12 34.5").unwrap();
+        assert_eq!(
+            (decimal_minutes.minutes, decimal_minutes.seconds),
+            (34, 30.0)
+        );
+
+        let decimal = parse_sexagesimal("-12.5").unwrap();
+        assert!(decimal.negative);
+        assert_eq!(
+            (decimal.whole, decimal.minutes, decimal.seconds),
+            (12, 30, 0.0)
+        );
+        assert_eq!(parse_sexagesimal("187.25°").unwrap().value(), 187.25);
+    }
+
+    #[test]
+    fn test_parse_reports_error_position() {
+        let err = parse_sexagesimal("12:3x:00").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedCharacter('x'));
+        assert_eq!(err.position, 4);
+
+        let err = parse_sexagesimal("12 60 00").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::OutOfRange);
+        assert_eq!(err.position, 3);
+
+        let err = parse_sexagesimal("12.5 30").unwrap_err();
+        assert_eq!(err.position, 5);
+
+        assert_eq!(
+            parse_sexagesimal("12:").unwrap_err().kind,
+            ParseErrorKind::UnexpectedEnd
+        );
+        assert_eq!(
+            parse_sexagesimal(" ").unwrap_err().kind,
+            ParseErrorKind::Empty
+        );
+    }
+
+    #[test]
+    fn test_parse_iso_date() {
+        let date = parse_iso_date("-0044-03-15T12:30:15.5-01:30").unwrap();
+        assert_eq!((date.year, date.month, date.day), (-44, 3, 15));
+        assert_eq!((date.hours, date.minutes, date.seconds), (12, 30, 15.5));
+        assert_eq!(date.offset, Some(-5400));
+        assert_eq!(parse_iso_date("2000-01-01").unwrap().offset, None);
+        assert_eq!(
+            parse_iso_date("2000-01-01T00:00+05").unwrap().offset,
+            Some(18_000)
+        );
+
+        let err = parse_iso_date("2000-02-30").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::OutOfRange);
+        assert_eq!(err.position, 8);
+
+        let err = parse_iso_date("2000-02-03X00:00").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedCharacter('X'));
+        assert_eq!(err.position, 10);
+
+        assert_eq!(
+            parse_iso_date("2000-02").unwrap_err().kind,
+            ParseErrorKind::UnexpectedEnd
+        );
+        assert_eq!(parse_iso_date("").unwrap_err().kind, ParseErrorKind::Empty);
+    }
+}
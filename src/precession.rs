@@ -0,0 +1,216 @@
+//! Precession — Meeus, *Astronomical Algorithms*, 2nd ed., Chapter 21.
+//!
+//! Rigorous reduction of equatorial and ecliptic coordinates between two
+//! arbitrary epochs. For reducing many positions between the same epochs
+//! compute the [`RotationMatrix`] once with [`get_precession_matrix`] and
+//! apply it with [`precess_with_matrix`].
+
+use crate::ecliptic::EclipticPosition;
+use crate::julian_day::JulianDay;
+use crate::{fit_degrees, Epoch, EqPosition};
+
+/// 3x3 rotation matrix acting on rectangular coordinates, see [`EqPosition::to_cartesian`].
+pub type RotationMatrix = [[f64; 3]; 3];
+
+/// Precession angles ζ, z and θ in degrees (Meeus eq. 21.2).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrecessionAngles {
+    pub zeta: f64,
+    pub z: f64,
+    pub theta: f64,
+}
+
+/// Julian centuries T from J2000 to the starting epoch and t from the starting
+/// to the final epoch.
+fn centuries(from: &JulianDay, to: &JulianDay) -> (f64, f64) {
+    let big_t = (from.get_value() - 2_451_545.0) / 36_525.0;
+    let t = (to.get_value() - from.get_value()) / 36_525.0;
+    (big_t, t)
+}
+
+/// Returns the precession angles for reducing equatorial coordinates from the
+/// equinox `from` to the equinox `to` (Meeus eq. 21.2).
+#[must_use]
+pub fn get_precession_angles(from: &JulianDay, to: &JulianDay) -> PrecessionAngles {
+    let (big_t, t) = centuries(from, to);
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let common = 2306.2181 + 1.396_56 * big_t - 0.000_139 * big_t * big_t;
+    let zeta = common * t + (0.301_88 - 0.000_344 * big_t) * t2 + 0.017_998 * t3;
+    let z = common * t + (1.094_68 + 0.000_066 * big_t) * t2 + 0.018_203 * t3;
+    let theta = (2004.3109 - 0.853_30 * big_t - 0.000_217 * big_t * big_t) * t
+        - (0.426_65 + 0.000_217 * big_t) * t2
+        - 0.041_833 * t3;
+
+    PrecessionAngles {
+        zeta: zeta / 3_600.0,
+        z: z / 3_600.0,
+        theta: theta / 3_600.0,
+    }
+}
+
+/// Returns the rotation matrix reducing rectangular equatorial coordinates from
+/// the equinox `from` to the equinox `to`, equivalent to Meeus eq. 21.4.
+#[must_use]
+pub fn get_precession_matrix(from: &JulianDay, to: &JulianDay) -> RotationMatrix {
+    let angles = get_precession_angles(from, to);
+    let (sin_zeta, cos_zeta) = angles.zeta.to_radians().sin_cos();
+    let (sin_z, cos_z) = angles.z.to_radians().sin_cos();
+    let (sin_theta, cos_theta) = angles.theta.to_radians().sin_cos();
+
+    [
+        [
+            cos_zeta * cos_theta * cos_z - sin_zeta * sin_z,
+            -sin_zeta * cos_theta * cos_z - cos_zeta * sin_z,
+            -sin_theta * cos_z,
+        ],
+        [
+            cos_zeta * cos_theta * sin_z + sin_zeta * cos_z,
+            -sin_zeta * cos_theta * sin_z + cos_zeta * cos_z,
+            -sin_theta * sin_z,
+        ],
+        [cos_zeta * sin_theta, -sin_zeta * sin_theta, cos_theta],
+    ]
+}
+
+/// Multiplies a rectangular vector by a rotation matrix.
+#[must_use]
+pub fn apply_rotation(matrix: &RotationMatrix, v: [f64; 3]) -> [f64; 3] {
+    let mut res = [0.0; 3];
+    for (row, value) in matrix.iter().zip(res.iter_mut()) {
+        *value = row[0] * v[0] + row[1] * v[1] + row[2] * v[2];
+    }
+    res
+}
+
+/// Applies a precession matrix from [`get_precession_matrix`] to a position,
+/// tagging the result with the final `epoch`.
+#[must_use]
+pub fn precess_with_matrix(pos: &EqPosition, matrix: &RotationMatrix, epoch: Epoch) -> EqPosition {
+    EqPosition::from_cartesian(apply_rotation(matrix, pos.to_cartesian())).with_epoch(epoch)
+}
+
+/// Precesses equatorial coordinates from the equinox `from` to the equinox `to`
+/// (libnova's `ln_get_equ_prec2`). The result is referred to [`Epoch::OfDate`] `to`.
+#[must_use]
+pub fn get_equ_prec2(pos: &EqPosition, from: &JulianDay, to: &JulianDay) -> EqPosition {
+    precess_with_matrix(pos, &get_precession_matrix(from, to), Epoch::OfDate(*to))
+}
+
+/// Precesses a position from the equinox it is tagged with to the equinox `to`.
+#[must_use]
+pub fn precess_equ(pos: &EqPosition, to: Epoch) -> EqPosition {
+    let matrix = get_precession_matrix(&pos.epoch().to_julian_day(), &to.to_julian_day());
+    precess_with_matrix(pos, &matrix, to)
+}
+
+/// Precesses ecliptic coordinates from the equinox `from` to the equinox `to`
+/// (Meeus eqs. 21.5 and 21.7).
+#[must_use]
+pub fn get_ecl_prec(ecl: &EclipticPosition, from: &JulianDay, to: &JulianDay) -> EclipticPosition {
+    let (big_t, t) = centuries(from, to);
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let eta = ((47.0029 - 0.066_03 * big_t + 0.000_598 * big_t * big_t) * t
+        + (-0.033_02 + 0.000_598 * big_t) * t2
+        + 0.000_060 * t3)
+        / 3_600.0;
+    let pi = 174.876_384 + (3289.4789 * big_t + 0.606_22 * big_t * big_t) / 3_600.0
+        - ((869.8089 + 0.504_91 * big_t) * t - 0.035_36 * t2) / 3_600.0;
+    let p = ((5029.0966 + 2.222_26 * big_t - 0.000_042 * big_t * big_t) * t
+        + (1.111_13 - 0.000_042 * big_t) * t2
+        - 0.000_006 * t3)
+        / 3_600.0;
+
+    let eta = eta.to_radians();
+    let beta0 = ecl.latitude.to_radians();
+    let d_lon = (pi - ecl.longitude).to_radians();
+
+    let a = eta.cos() * beta0.cos() * d_lon.sin() - eta.sin() * beta0.sin();
+    let b = beta0.cos() * d_lon.cos();
+    let c = eta.cos() * beta0.sin() + eta.sin() * beta0.cos() * d_lon.sin();
+
+    EclipticPosition::new(
+        fit_degrees(p + pi - a.atan2(b).to_degrees()),
+        c.asin().to_degrees(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Declination, RightAscension};
+
+    #[test]
+    fn test_precession_meeus_example_21b() {
+        // Meeus, Astronomical Algorithms, 2nd ed., Example 21.b, p. 135
+        // θ Persei J2000 with proper motion applied to 2028 Nov 13.19 TD:
+        // α0 = 41.054063°, δ0 = 49.227750°
+        // Expected: α = 41.547214°, δ = 49.348483°
+        let from = JulianDay::new(2_451_545.0);
+        let to = JulianDay::new(2_462_088.69);
+        let pos = EqPosition::from_degrees(41.054_063, 49.227_750);
+        let precessed = get_equ_prec2(&pos, &from, &to);
+
+        assert!(
+            (precessed.ra_degrees() - 41.547_214).abs() < 1e-6,
+            "ra: {}",
+            precessed.ra_degrees()
+        );
+        assert!(
+            (precessed.dec_degrees() - 49.348_483).abs() < 1e-6,
+            "dec: {}",
+            precessed.dec_degrees()
+        );
+        assert_eq!(precessed.epoch(), Epoch::OfDate(to));
+    }
+
+    #[test]
+    fn test_precess_equ_between_tagged_epochs() {
+        let pos = EqPosition::new(
+            RightAscension::new(10, 8, 22.3),
+            Declination::new(11, 58, 2.0),
+        );
+        let b1950 = precess_equ(&pos, Epoch::B1950);
+        assert_eq!(b1950.epoch(), Epoch::B1950);
+
+        let back = precess_equ(&b1950, Epoch::J2000);
+        assert!((back.ra_degrees() - pos.ra_degrees()).abs() < 1e-9);
+        assert!((back.dec_degrees() - pos.dec_degrees()).abs() < 1e-9);
+        // Regulus is catalogued at 10h05m42.6s +12°12'44" B1950, the residual
+        // difference comes from proper motion and the FK4 frame corrections.
+        let catalogue = EqPosition::from_string("10 05 42.6", "12 12 44");
+        assert!((b1950.ra_degrees() - catalogue.ra_degrees()).abs() < 0.01);
+        assert!((b1950.dec_degrees() - catalogue.dec_degrees()).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_precession_matrix_is_orthogonal() {
+        let m = get_precession_matrix(&JulianDay::new(2_451_545.0), &JulianDay::new(2_469_807.5));
+        for i in 0..3 {
+            for j in 0..3 {
+                let dot: f64 = (0..3).map(|k| m[i][k] * m[j][k]).sum();
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((dot - expected).abs() < 1e-15);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ecl_precession_meeus_example_21c() {
+        // Meeus, Astronomical Algorithms, 2nd ed., Example 21.c, p. 137
+        // Venus at J2000: λ0 = 149.48194°, β0 = 1.76549°
+        // To −214 June 30.0 (JDE 1643074.5): λ = 118.704°, β = 1.615°
+        let ecl = EclipticPosition::new(149.481_94, 1.765_49);
+        let res = get_ecl_prec(
+            &ecl,
+            &JulianDay::new(2_451_545.0),
+            &JulianDay::new(1_643_074.5),
+        );
+
+        assert!((res.longitude - 118.704).abs() < 0.001, "{res:?}");
+        assert!((res.latitude - 1.615).abs() < 0.001, "{res:?}");
+    }
+}
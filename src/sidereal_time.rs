@@ -1,29 +1,245 @@
 use crate::date::Date;
-use crate::fit_degrees;
+use crate::julian_day::JulianDay;
+use crate::nutation::{get_nutation, get_true_obliquity};
+use crate::{fit_degrees, Error, HoursMinSec, LongLatPosition};
 
+/// A sidereal time, both as an angle and as a time of the sidereal day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SiderealTime {
+    /// Sidereal time in degrees, in the range 0-360.
+    pub degrees: f64,
+    /// Sidereal time in hours, minutes and seconds.
+    pub hms: HoursMinSec,
+}
+
+impl SiderealTime {
+    /// Creates a sidereal time from an angle in degrees, reduced to the range 0-360.
+    #[must_use]
+    pub fn from_degrees(degrees: f64) -> Self {
+        let degrees = fit_degrees(degrees);
+        Self {
+            degrees,
+            hms: HoursMinSec::from_degrees(degrees),
+        }
+    }
+}
+
+/// Returns the mean sidereal time at Greenwich for the given Julian Day (UT1),
+/// Meeus eq. 12.4.
 #[must_use]
-pub fn get_mean_sidereal_time_from_date(date: &Date) -> f64 {
-    let jd = date.to_julian_day().get_value();
-    let t = (&jd - 2_451_545_f64) / 36525_f64;
+pub fn get_mean_sidereal_time(jd: &JulianDay) -> SiderealTime {
+    let days = jd.days_since(&JulianDay::new(2_451_545_f64));
+    let t = days / 36525_f64;
 
     #[cfg(target_feature = "fma")]
-    let theta = 0.000_387_933f64.mul_add(
-        t * t,
-        360.985_647_366_29f64.mul_add(&jd - 2_451_545_f64, 280.460_618_37),
-    );
+    let theta = (0.000_387_933 - t / 38_710_000_f64)
+        .mul_add(t * t, 360.985_647_366_29f64.mul_add(days, 280.460_618_37));
 
     #[cfg(not(target_feature = "fma"))]
-    let theta =
-        280.460_618_37 + 360.985_647_366_29 * (&jd - 2_451_545_f64) + (0.000_387_933 * (t * t))
-            - ((t * t * t) / 38_710_000_f64);
+    let theta = 280.460_618_37 + 360.985_647_366_29 * days + (0.000_387_933 * (t * t))
+        - ((t * t * t) / 38_710_000_f64);
+
+    SiderealTime::from_degrees(theta)
+}
+
+/// Returns the equation of the equinoxes Δψ·cos ε in degrees, the difference
+/// between apparent and mean sidereal time (Meeus p. 88).
+#[must_use]
+pub fn get_equation_of_equinoxes(jd: &JulianDay) -> f64 {
+    get_nutation(jd).delta_psi / 3_600.0 * get_true_obliquity(jd).to_radians().cos()
+}
+
+/// Returns the apparent sidereal time at Greenwich for the given Julian Day
+/// (UT1), the mean sidereal time corrected by the equation of the equinoxes.
+#[must_use]
+pub fn get_apparent_sidereal_time(jd: &JulianDay) -> SiderealTime {
+    SiderealTime::from_degrees(get_mean_sidereal_time(jd).degrees + get_equation_of_equinoxes(jd))
+}
+
+/// Returns the local mean sidereal time for an observer, whose longitude is
+/// positive east of Greenwich.
+#[must_use]
+pub fn get_local_mean_sidereal_time(jd: &JulianDay, observer: &LongLatPosition) -> SiderealTime {
+    SiderealTime::from_degrees(get_mean_sidereal_time(jd).degrees + observer.long_degrees())
+}
+
+/// Returns the local apparent sidereal time for an observer, whose longitude is
+/// positive east of Greenwich.
+#[must_use]
+pub fn get_local_apparent_sidereal_time(
+    jd: &JulianDay,
+    observer: &LongLatPosition,
+) -> SiderealTime {
+    SiderealTime::from_degrees(get_apparent_sidereal_time(jd).degrees + observer.long_degrees())
+}
+
+/// Returns the Earth Rotation Angle θ in degrees for the given Julian Day (UT1),
+/// IERS Conventions (2010) eq. 5.15.
+#[must_use]
+pub fn get_earth_rotation_angle(jd: &JulianDay) -> f64 {
+    let days = jd.days_since(&JulianDay::new(2_451_545_f64));
+    // The Earth turns 1.00273781191135448 times per UT1 day, the whole turn
+    // is taken from the fraction of the day alone to preserve precision.
+    let turns = jd.fraction() + 0.779_057_273_264 + 0.002_737_811_911_354_48 * days;
+
+    fit_degrees(turns.rem_euclid(1_f64) * 360_f64)
+}
+
+/// Returns the Greenwich mean sidereal time of the IAU 2006 precession model,
+/// consistent with the Earth Rotation Angle (Capitaine et al. 2003, SOFA's
+/// `iauGmst06`). `ut1` gives the rotation angle and `tt` the precession terms.
+#[must_use]
+pub fn get_mean_sidereal_time_iau2006(ut1: &JulianDay, tt: &JulianDay) -> SiderealTime {
+    let t = (tt.get_value() - 2_451_545_f64) / 36525_f64;
+    let arcsec = 0.014_506
+        + (4_612.156_534
+            + (1.391_581_7 + (-0.000_000_44 + (-0.000_029_956 - 0.000_000_036_8 * t) * t) * t) * t)
+            * t;
+
+    SiderealTime::from_degrees(get_earth_rotation_angle(ut1) + arcsec / 3_600_f64)
+}
+
+/// Returns the Greenwich apparent sidereal time of the IAU 2006 model, the
+/// [`get_mean_sidereal_time_iau2006`] plus the equation of the equinoxes with its
+/// complementary terms (IERS Conventions (2010) eq. 5.30).
+///
+/// The nutation in longitude comes from [`get_nutation`] (IAU 1980), which
+/// agrees with the IAU 2000A series used by SOFA's `iauGst06a` to a few
+/// milliarcseconds.
+#[must_use]
+pub fn get_apparent_sidereal_time_iau2006(ut1: &JulianDay, tt: &JulianDay) -> SiderealTime {
+    let t = (tt.get_value() - 2_451_545_f64) / 36525_f64;
+    // Mean obliquity of the IAU 2006 precession in arcseconds
+    let epsilon = 84_381.406
+        + (-46.836_769
+            + (-0.000_183_1 + (0.002_003_40 + (-0.000_000_576 - 0.000_000_043_4 * t) * t) * t) * t)
+            * t;
+    let omega = (125.044_555_01 - 1_934.136_261_97 * t).to_radians();
+
+    let equation = get_nutation(tt).delta_psi * (epsilon / 3_600_f64).to_radians().cos()
+        + 0.002_640_96 * omega.sin()
+        + 0.000_063_52 * (2_f64 * omega).sin();
+
+    SiderealTime::from_degrees(
+        get_mean_sidereal_time_iau2006(ut1, tt).degrees + equation / 3_600_f64,
+    )
+}
+
+/// Model used to compute the sidereal time at Greenwich.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SiderealModel {
+    /// IAU 1982 polynomial in UT1 (Meeus eq. 12.4) with the IAU 1980 nutation.
+    #[default]
+    Meeus,
+    /// Earth Rotation Angle based IAU 2006 expressions.
+    Iau2006,
+}
+
+impl SiderealModel {
+    /// Greenwich mean sidereal time for the instant given both in UT1 and in TT.
+    #[must_use]
+    pub fn mean(self, ut1: &JulianDay, tt: &JulianDay) -> SiderealTime {
+        match self {
+            Self::Meeus => get_mean_sidereal_time(ut1),
+            Self::Iau2006 => get_mean_sidereal_time_iau2006(ut1, tt),
+        }
+    }
+
+    /// Greenwich apparent sidereal time for the instant given both in UT1 and in TT.
+    #[must_use]
+    pub fn apparent(self, ut1: &JulianDay, tt: &JulianDay) -> SiderealTime {
+        match self {
+            Self::Meeus => SiderealTime::from_degrees(
+                get_mean_sidereal_time(ut1).degrees + get_equation_of_equinoxes(tt),
+            ),
+            Self::Iau2006 => get_apparent_sidereal_time_iau2006(ut1, tt),
+        }
+    }
+}
+
+/// Returns the mean sidereal time at Greenwich in degrees.
+///
+/// # Panics
+///
+/// Panics if the month of `date` is invalid, see [`try_get_mean_sidereal_time_from_date`].
+#[must_use]
+pub fn get_mean_sidereal_time_from_date(date: &Date) -> f64 {
+    try_get_mean_sidereal_time_from_date(date).expect("Error")
+}
+
+/// Fallible version of [`get_mean_sidereal_time_from_date`].
+pub fn try_get_mean_sidereal_time_from_date(date: &Date) -> Result<f64, Error> {
+    Ok(get_mean_sidereal_time(&date.try_to_julian_day()?).degrees)
+}
+
+/// Returns the apparent sidereal time at Greenwich, see [`get_apparent_sidereal_time`].
+///
+/// # Panics
+///
+/// Panics if the month of `date` is invalid, see [`try_get_apparent_sidereal_time_from_date`].
+#[must_use]
+pub fn get_apparent_sidereal_time_from_date(date: &Date) -> SiderealTime {
+    try_get_apparent_sidereal_time_from_date(date).expect("Error")
+}
+
+/// Fallible version of [`get_apparent_sidereal_time_from_date`].
+pub fn try_get_apparent_sidereal_time_from_date(date: &Date) -> Result<SiderealTime, Error> {
+    Ok(get_apparent_sidereal_time(&date.try_to_julian_day()?))
+}
+
+/// Returns the local mean sidereal time for an observer, see [`get_local_mean_sidereal_time`].
+///
+/// # Panics
+///
+/// Panics if the month of `date` is invalid, see [`try_get_local_mean_sidereal_time_from_date`].
+#[must_use]
+pub fn get_local_mean_sidereal_time_from_date(
+    date: &Date,
+    observer: &LongLatPosition,
+) -> SiderealTime {
+    try_get_local_mean_sidereal_time_from_date(date, observer).expect("Error")
+}
+
+/// Fallible version of [`get_local_mean_sidereal_time_from_date`].
+pub fn try_get_local_mean_sidereal_time_from_date(
+    date: &Date,
+    observer: &LongLatPosition,
+) -> Result<SiderealTime, Error> {
+    Ok(get_local_mean_sidereal_time(
+        &date.try_to_julian_day()?,
+        observer,
+    ))
+}
+
+/// Returns the local apparent sidereal time for an observer, see
+/// [`get_local_apparent_sidereal_time`].
+///
+/// # Panics
+///
+/// Panics if the month of `date` is invalid, see [`try_get_local_apparent_sidereal_time_from_date`].
+#[must_use]
+pub fn get_local_apparent_sidereal_time_from_date(
+    date: &Date,
+    observer: &LongLatPosition,
+) -> SiderealTime {
+    try_get_local_apparent_sidereal_time_from_date(date, observer).expect("Error")
+}
 
-    fit_degrees(theta)
+/// Fallible version of [`get_local_apparent_sidereal_time_from_date`].
+pub fn try_get_local_apparent_sidereal_time_from_date(
+    date: &Date,
+    observer: &LongLatPosition,
+) -> Result<SiderealTime, Error> {
+    Ok(get_local_apparent_sidereal_time(
+        &date.try_to_julian_day()?,
+        observer,
+    ))
 }
 
 #[cfg(test)]
 mod test {
     use crate::date::Date;
-    use crate::sidereal_time::get_mean_sidereal_time_from_date;
+    use crate::sidereal_time::*;
     use crate::RightAscension;
 
     #[test]
@@ -36,13 +252,92 @@ mod test {
         assert_eq!(RightAscension::from_degrees(mst), expected_ra);
     }
 
-    // #[test]
-    // fn test_mean_sidereal_time_2() {
-    // 	// Example 12.b p.89 from Meeus book 2nd edition
-    // 	let date = Date::new(1987, 4, 10.0);
-    // 	let mst = get_mean_sidereal_time_from_date(&date);
-    // 	let expected_ra = RightAscension::new(13, 10, 46.3668);
+    #[test]
+    fn test_apparent_sidereal_time() {
+        // Example 12.a p.88 from Meeus book 2nd edition
+        let date = Date::new(1987, 4, 10.0);
+        let ast = get_apparent_sidereal_time_from_date(&date);
+
+        let expected = RightAscension::new(13, 10, 46.1351);
+
+        assert_eq!((ast.hms.hours, ast.hms.minutes), (13, 10));
+        assert!((ast.hms.seconds - expected.seconds).abs() < 1e-3);
+        assert!((ast.degrees * 240.0 - 47_446.135_1).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_local_sidereal_time() {
+        // Mount Palomar, 116°51'50" W
+        let date = Date::new(1987, 4, 10.0);
+        let palomar = LongLatPosition::from_degrees(-116.863_889, 33.356_111);
+        let mean = get_mean_sidereal_time_from_date(&date);
+
+        let lmst = get_local_mean_sidereal_time_from_date(&date, &palomar);
+        assert!((lmst.degrees - (mean - 116.863_889)).abs() < 1e-9);
+
+        let last = get_local_apparent_sidereal_time_from_date(&date, &palomar);
+        let ast = get_apparent_sidereal_time_from_date(&date);
+        assert!((last.degrees - (ast.degrees - 116.863_889)).abs() < 1e-9);
+        assert!(((last.degrees - lmst.degrees) * 240.0 + 0.2317).abs() < 1e-3);
+    }
 
-    // 	assert_eq!(RightAscension::from_degrees(mst), expected_ra);
-    // }
+    #[test]
+    fn test_mean_sidereal_time_2() {
+        // Example 12.b p.89 from Meeus book 2nd edition
+        let date = Date::from_full_date(1987, 4, 10.0, 19, 21, 0.0);
+        let mst = get_mean_sidereal_time_from_date(&date);
+        let expected_ra = RightAscension::new(8, 34, 57.0896);
+
+        assert_eq!(RightAscension::from_degrees(mst), expected_ra);
+    }
+
+    #[test]
+    fn test_sidereal_time_from_julian_day() {
+        // Examples 12.a and 12.b p.88-89 from Meeus book 2nd edition
+        let jd = JulianDay::new(2_446_895.5);
+        assert_eq!(
+            get_mean_sidereal_time(&jd).hms,
+            RightAscension::new(13, 10, 46.3668)
+        );
+        let ast = get_apparent_sidereal_time(&jd);
+        assert!((ast.degrees * 240.0 - 47_446.135_1).abs() < 1e-3);
+
+        let jd = JulianDay::new(2_446_896.306_25);
+        let mst = get_mean_sidereal_time(&jd);
+        assert_eq!(mst.hms, RightAscension::new(8, 34, 57.0896));
+        assert!((mst.degrees - 128.737_873_4).abs() < 1e-6);
+    }
+
+    // SOFA test suite (t_sofa_c.c) reference values in radians. The apparent
+    // sidereal times differ by ~1 mas because of the IAU 1980 nutation.
+    const SOFA_JD: f64 = 2_453_736.5;
+
+    #[test]
+    fn test_earth_rotation_angle_sofa() {
+        let era = get_earth_rotation_angle(&JulianDay::new(2_454_388.5));
+        assert!((era.to_radians() - 0.402_283_724_002_815_8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_iau2006_sidereal_time_sofa() {
+        let jd = JulianDay::new(SOFA_JD);
+
+        let gmst = SiderealModel::Iau2006.mean(&jd, &jd);
+        assert!((gmst.degrees.to_radians() - 1.754_174_971_870_091).abs() < 1e-12);
+
+        let gast = SiderealModel::Iau2006.apparent(&jd, &jd);
+        assert!((gast.degrees.to_radians() - 1.754_166_137_675_019).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_meeus_sidereal_time_sofa() {
+        // iauGmst82 and iauGst94 for the same instant
+        let jd = JulianDay::new(SOFA_JD);
+
+        let gmst = SiderealModel::Meeus.mean(&jd, &jd);
+        assert!((gmst.degrees.to_radians() - 1.754_174_981_860_675).abs() < 1e-10);
+
+        let gast = SiderealModel::Meeus.apparent(&jd, &jd);
+        assert!((gast.degrees.to_radians() - 1.754_166_136_020_645).abs() < 1e-8);
+    }
 }
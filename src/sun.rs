@@ -0,0 +1,138 @@
+//! Position of the Sun — Meeus, *Astronomical Algorithms*, 2nd ed., Chapter 25.
+//!
+//! Low accuracy theory (0.01°) based on the geometric mean longitude and the
+//! equation of the centre, referred to the mean equinox of date.
+
+use crate::ecliptic::{ecl_to_equ, EclipticPosition};
+use crate::julian_day::JulianDay;
+use crate::nutation::get_mean_obliquity;
+use crate::{fit_degrees, Epoch, EqPosition};
+
+/// Geocentric position of the Sun.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunPosition {
+    /// True geometric longitude ☉ in degrees, referred to the mean equinox of date.
+    pub true_longitude: f64,
+    /// Apparent longitude λ in degrees, corrected for nutation and aberration.
+    pub apparent_longitude: f64,
+    /// Distance between the centres of the Earth and the Sun in AU.
+    pub radius_vector: f64,
+}
+
+/// Computes the geocentric position of the Sun for the given Julian Ephemeris Day.
+///
+/// Algorithm: Meeus eqs. 25.2–25.5 and p. 164.
+#[must_use]
+pub fn get_sun_position(jd: &JulianDay) -> SunPosition {
+    let t = (jd.get_value() - 2_451_545.0) / 36_525.0;
+    let t2 = t * t;
+
+    // Geometric mean longitude L0 and mean anomaly M (eqs. 25.2 and 25.3)
+    let l0 = 280.466_46 + 36_000.769_83 * t + 0.000_303_2 * t2;
+    let m = (357.529_11 + 35_999.050_29 * t - 0.000_153_7 * t2).to_radians();
+    // Eccentricity of the Earth's orbit (eq. 25.4)
+    let e = 0.016_708_634 - 0.000_042_037 * t - 0.000_000_126_7 * t2;
+
+    // Equation of the centre C
+    let c = (1.914_602 - 0.004_817 * t - 0.000_014 * t2) * m.sin()
+        + (0.019_993 - 0.000_101 * t) * (2.0 * m).sin()
+        + 0.000_289 * (3.0 * m).sin();
+
+    let true_longitude = fit_degrees(l0 + c);
+    let true_anomaly = m + c.to_radians();
+    // eq. 25.5
+    let radius_vector = 1.000_001_018 * (1.0 - e * e) / (1.0 + e * true_anomaly.cos());
+
+    let omega = (125.04 - 1_934.136 * t).to_radians();
+    let apparent_longitude = fit_degrees(true_longitude - 0.005_69 - 0.004_78 * omega.sin());
+
+    SunPosition {
+        true_longitude,
+        apparent_longitude,
+        radius_vector,
+    }
+}
+
+/// Geometric rectangular equatorial coordinates of the Sun in AU, referred to
+/// the mean equator and equinox of date.
+#[must_use]
+pub fn get_sun_rect_coords(jd: &JulianDay) -> [f64; 3] {
+    let sun = get_sun_position(jd);
+    let direction = ecl_to_equ(
+        &EclipticPosition::new(sun.true_longitude, 0.0),
+        get_mean_obliquity(jd),
+    )
+    .to_cartesian();
+
+    direction.map(|x| x * sun.radius_vector)
+}
+
+/// Velocity of the Earth in AU/day in rectangular equatorial coordinates
+/// referred to the mean equator and equinox of date, derived from the motion of
+/// the Sun in [`get_sun_rect_coords`].
+///
+/// This is the heliocentric velocity of the Earth–Moon barycentre; it differs
+/// from the barycentric velocity of the Earth by about 0.03 km/s, which is
+/// below 0.03" once used for aberration.
+#[must_use]
+pub fn get_earth_velocity(jd: &JulianDay) -> [f64; 3] {
+    let step = 0.01;
+    let before = get_sun_rect_coords(&JulianDay::new(jd.get_value() - step));
+    let after = get_sun_rect_coords(&JulianDay::new(jd.get_value() + step));
+
+    [0, 1, 2].map(|i| (before[i] - after[i]) / (2.0 * step))
+}
+
+/// Apparent equatorial position of the Sun, referred to the true equinox of date.
+#[must_use]
+pub fn get_sun_equ_coords(jd: &JulianDay) -> EqPosition {
+    let t = (jd.get_value() - 2_451_545.0) / 36_525.0;
+    let omega = (125.04 - 1_934.136 * t).to_radians();
+    // Meeus eq. 25.8
+    let obliquity = get_mean_obliquity(jd) + 0.002_56 * omega.cos();
+
+    ecl_to_equ(
+        &EclipticPosition::new(get_sun_position(jd).apparent_longitude, 0.0),
+        obliquity,
+    )
+    .with_epoch(Epoch::OfDate(*jd))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sun_position_meeus_example_25a() {
+        // Meeus, Astronomical Algorithms, 2nd ed., Example 25.a, p. 165
+        // 1992 October 13.0 TD = JDE 2448908.5
+        // Expected: ☉ = 199.90988°, R = 0.99766 AU, λ = 199.90895°,
+        //           α = 198.38083°, δ = −7.78507°
+        let jd = JulianDay::new(2_448_908.5);
+        let sun = get_sun_position(&jd);
+
+        assert!(
+            (sun.true_longitude - 199.909_88).abs() < 1e-5,
+            "{}",
+            sun.true_longitude
+        );
+        assert!(
+            (sun.apparent_longitude - 199.908_95).abs() < 1e-5,
+            "{}",
+            sun.apparent_longitude
+        );
+        assert!((sun.radius_vector - 0.997_66).abs() < 1e-5);
+
+        let pos = get_sun_equ_coords(&jd);
+        assert!((pos.ra_degrees() - 198.380_83).abs() < 1e-5);
+        assert!((pos.dec_degrees() + 7.785_07).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_earth_velocity() {
+        // The orbital speed of the Earth is close to 29.3 km/s at aphelion.
+        let v = get_earth_velocity(&JulianDay::new(2_460_496.5));
+        let speed = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt() * 149_597_870.7 / 86_400.0;
+        assert!((speed - 29.3).abs() < 0.1, "speed: {speed}");
+    }
+}
@@ -0,0 +1,342 @@
+//! Astronomical time scales.
+//!
+//! An [`Instant`] is a Julian Day tagged with the [`TimeScale`] it is expressed
+//! in and can be converted between UTC, TAI, TT, TDB and UT1:
+//!
+//! - UTC ⇄ TAI with the built-in table of leap seconds (1972 onwards),
+//! - TAI ⇄ TT with the fixed offset TT − TAI = 32.184 s,
+//! - TT ⇄ TDB with the main periodic terms of TDB − TT (USNO Circular 179, eq. 2.6),
+//! - UT1 either from UTC and a user supplied DUT1 = UT1 − UTC, or from TT and
+//!   [`crate::dynamical_time::delta_t`].
+//!
+//! Algorithms taking a "Julian Ephemeris Day" expect TT (or TDB, which differs
+//! by less than 2 ms), sidereal time expects UT1.
+
+use std::ops::{Add, Sub};
+
+use crate::duration::Duration;
+use crate::dynamical_time::try_delta_t;
+use crate::julian_day::JulianDay;
+use crate::Error;
+
+/// TT − TAI in seconds.
+pub const TT_MINUS_TAI: f64 = 32.184;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// MJD of the first UTC day of each TAI − UTC value, with the value in seconds.
+#[rustfmt::skip]
+static LEAP_SECONDS: &[(i32, f64)] = &[
+    (41_317, 10.0), (41_499, 11.0), (41_683, 12.0), (42_048, 13.0),
+    (42_413, 14.0), (42_778, 15.0), (43_144, 16.0), (43_509, 17.0),
+    (43_874, 18.0), (44_239, 19.0), (44_786, 20.0), (45_151, 21.0),
+    (45_516, 22.0), (46_247, 23.0), (47_161, 24.0), (47_892, 25.0),
+    (48_257, 26.0), (48_804, 27.0), (49_169, 28.0), (49_534, 29.0),
+    (50_083, 30.0), (50_630, 31.0), (51_179, 32.0), (53_736, 33.0),
+    (54_832, 34.0), (56_109, 35.0), (57_204, 36.0), (57_754, 37.0),
+];
+
+/// A time scale an instant can be expressed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    /// Coordinated Universal Time.
+    Utc,
+    /// International Atomic Time.
+    Tai,
+    /// Terrestrial Time, the successor of Ephemeris Time and TD of Meeus.
+    Tt,
+    /// Barycentric Dynamical Time.
+    Tdb,
+    /// Universal Time, following the rotation of the Earth.
+    Ut1,
+}
+
+/// A Julian Day expressed in a given [`TimeScale`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Instant {
+    jd: JulianDay,
+    scale: TimeScale,
+    dut1: Option<f64>,
+}
+
+impl Instant {
+    #[must_use]
+    pub fn new(jd: JulianDay, scale: TimeScale) -> Self {
+        Self {
+            jd,
+            scale,
+            dut1: None,
+        }
+    }
+
+    /// Sets DUT1 = UT1 − UTC in seconds, as published by the IERS, used for
+    /// the conversions to and from UT1 instead of ΔT.
+    #[must_use]
+    pub fn with_dut1(mut self, dut1: f64) -> Self {
+        self.dut1 = Some(dut1);
+        self
+    }
+
+    #[must_use]
+    pub fn jd(&self) -> JulianDay {
+        self.jd
+    }
+
+    #[must_use]
+    pub fn scale(&self) -> TimeScale {
+        self.scale
+    }
+
+    #[must_use]
+    pub fn dut1(&self) -> Option<f64> {
+        self.dut1
+    }
+
+    /// Converts the instant to another time scale.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ΔT is needed and not available, see [`Instant::try_to_scale`].
+    #[must_use]
+    pub fn to_scale(&self, scale: TimeScale) -> Self {
+        self.try_to_scale(scale).expect("Not supported")
+    }
+
+    /// Converts the instant to another time scale, returning
+    /// [`Error::DeltaTOutOfRange`] if a conversion involving UT1 without DUT1
+    /// cannot evaluate [`try_delta_t`].
+    pub fn try_to_scale(&self, scale: TimeScale) -> Result<Self, Error> {
+        if scale == self.scale {
+            return Ok(*self);
+        }
+
+        let tai = self.tai_value()?;
+        let jd = match scale {
+            TimeScale::Tai => tai,
+            TimeScale::Utc => tai_to_utc(tai),
+            TimeScale::Tt => tai + TT_MINUS_TAI / SECONDS_PER_DAY,
+            TimeScale::Tdb => {
+                let tt = tai + TT_MINUS_TAI / SECONDS_PER_DAY;
+                tt + get_tdb_minus_tt(&tt) / SECONDS_PER_DAY
+            }
+            TimeScale::Ut1 => match self.dut1 {
+                Some(dut1) => tai_to_utc(tai) + dut1 / SECONDS_PER_DAY,
+                None => {
+                    let tt = tai + TT_MINUS_TAI / SECONDS_PER_DAY;
+                    tt - delta_t_at(&tt)? / SECONDS_PER_DAY
+                }
+            },
+        };
+
+        Ok(Self {
+            jd,
+            scale,
+            dut1: self.dut1,
+        })
+    }
+
+    #[must_use]
+    pub fn to_utc(&self) -> Self {
+        self.to_scale(TimeScale::Utc)
+    }
+
+    #[must_use]
+    pub fn to_tai(&self) -> Self {
+        self.to_scale(TimeScale::Tai)
+    }
+
+    #[must_use]
+    pub fn to_tt(&self) -> Self {
+        self.to_scale(TimeScale::Tt)
+    }
+
+    #[must_use]
+    pub fn to_tdb(&self) -> Self {
+        self.to_scale(TimeScale::Tdb)
+    }
+
+    #[must_use]
+    pub fn to_ut1(&self) -> Self {
+        self.to_scale(TimeScale::Ut1)
+    }
+
+    /// Julian Day of the instant in TAI.
+    fn tai_value(&self) -> Result<JulianDay, Error> {
+        let jd = self.jd;
+
+        Ok(match self.scale {
+            TimeScale::Tai => jd,
+            TimeScale::Utc => jd + get_leap_seconds(&jd) / SECONDS_PER_DAY,
+            TimeScale::Tt => jd - TT_MINUS_TAI / SECONDS_PER_DAY,
+            TimeScale::Tdb => {
+                let tt = jd - get_tdb_minus_tt(&jd) / SECONDS_PER_DAY;
+                tt - TT_MINUS_TAI / SECONDS_PER_DAY
+            }
+            TimeScale::Ut1 => match self.dut1 {
+                Some(dut1) => {
+                    let utc = jd - dut1 / SECONDS_PER_DAY;
+                    utc + get_leap_seconds(&utc) / SECONDS_PER_DAY
+                }
+                None => jd + (delta_t_at(&jd)? - TT_MINUS_TAI) / SECONDS_PER_DAY,
+            },
+        })
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Self;
+
+    /// Adds a span to the Julian Day in the scale of the instant, a UTC instant
+    /// doesn't count leap seconds so spans across them are better added in TAI.
+    fn add(self, duration: Duration) -> Self {
+        Self {
+            jd: self.jd + duration,
+            ..self
+        }
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Self;
+
+    fn sub(self, duration: Duration) -> Self {
+        self + -duration
+    }
+}
+
+/// ΔT in seconds at the Julian Day `jd`.
+fn delta_t_at(jd: &JulianDay) -> Result<f64, Error> {
+    try_delta_t(&jd.try_to_calendar_date()?)
+}
+
+fn tai_to_utc(tai: JulianDay) -> JulianDay {
+    // The offset is looked up at the UTC date, which the first guess gets
+    // right except within a few seconds of a leap second.
+    let guess = tai - get_leap_seconds(&tai) / SECONDS_PER_DAY;
+    tai - get_leap_seconds(&guess) / SECONDS_PER_DAY
+}
+
+/// Returns TAI − UTC in seconds at the given UTC Julian Day.
+///
+/// Before 1972 UTC was not an integral number of seconds from TAI, the first
+/// value of 10 s is returned for those dates.
+#[must_use]
+pub fn get_leap_seconds(utc: &JulianDay) -> f64 {
+    let mjd = utc.to_modified_jd().floor();
+
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|(start, _)| mjd >= f64::from(*start))
+        .map_or(LEAP_SECONDS[0].1, |(_, seconds)| *seconds)
+}
+
+/// Returns TDB − TT in seconds at the given TT Julian Day, from the main
+/// periodic terms (USNO Circular 179, eq. 2.6), accurate to about 10 μs
+/// between 1600 and 2200.
+#[must_use]
+pub fn get_tdb_minus_tt(tt: &JulianDay) -> f64 {
+    let t = (tt.get_value() - 2_451_545.0) / 36_525.0;
+
+    0.001_657 * (628.307_6 * t + 6.240_1).sin()
+        + 0.000_022 * (575.338_5 * t + 4.297_0).sin()
+        + 0.000_014 * (1_256.615_2 * t + 6.196_9).sin()
+        + 0.000_005 * (606.977_7 * t + 4.021_2).sin()
+        + 0.000_005 * (52.969_1 * t + 0.444_4).sin()
+        + 0.000_002 * (21.329_9 * t + 5.543_1).sin()
+        + 0.000_010 * t * (628.307_6 * t + 4.249_0).sin()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // SOFA test suite (t_sofa_c.c): 2006 January 15 21:24:37.5 UTC
+    const UTC: f64 = 2_453_750.892_100_694;
+
+    fn assert_seconds(actual: &Instant, expected: f64, tolerance: f64) {
+        let diff = actual.jd().days_since(&JulianDay::new(expected)) * SECONDS_PER_DAY;
+        assert!(diff.abs() < tolerance, "{actual:?} off by {diff}s");
+    }
+
+    #[test]
+    fn test_utc_tai_tt_sofa() {
+        let utc = Instant::new(JulianDay::new(UTC), TimeScale::Utc);
+
+        let tai = utc.to_tai();
+        assert_eq!(tai.scale(), TimeScale::Tai);
+        assert_seconds(&tai, 2_453_750.892_482_638_4, 1e-4);
+        assert_seconds(&utc.to_tt(), 2_453_750.892_855_138_4, 1e-4);
+        assert_seconds(&tai.to_utc(), UTC, 1e-4);
+    }
+
+    #[test]
+    fn test_offsets_keep_microseconds() {
+        let utc = Instant::new(
+            JulianDay::from_parts(2_460_000.0, 0.123_456_789),
+            TimeScale::Utc,
+        );
+        let tt = utc.to_tt();
+        let offset = tt.jd().days_since(&utc.jd()) * SECONDS_PER_DAY;
+        assert!((offset - 69.184).abs() < 1e-9, "{offset}");
+        assert_eq!(tt.to_utc().jd(), utc.jd());
+    }
+
+    #[test]
+    fn test_ut1_from_dut1_sofa() {
+        let utc = Instant::new(JulianDay::new(UTC), TimeScale::Utc).with_dut1(0.3341);
+        let ut1 = utc.to_ut1();
+        assert_seconds(&ut1, 2_453_750.892_104_561, 1e-4);
+        assert_seconds(&ut1.to_utc(), UTC, 1e-4);
+        assert_seconds(&ut1.to_tt(), 2_453_750.892_855_138_4, 1e-4);
+    }
+
+    #[test]
+    fn test_ut1_from_delta_t() {
+        // ΔT was about 64.8 s at the beginning of 2006
+        let tt = Instant::new(JulianDay::new(2_453_750.892_855_138_4), TimeScale::Tt);
+        let ut1 = tt.to_ut1();
+        let delta_t = (tt.jd().get_value() - ut1.jd().get_value()) * SECONDS_PER_DAY;
+        assert!((delta_t - 64.8).abs() < 0.5, "ΔT = {delta_t}");
+        assert_seconds(&ut1.to_tt(), tt.jd().get_value(), 1e-3);
+
+        let invalid = Instant::new(JulianDay::new(f64::NAN), TimeScale::Tt);
+        assert!(invalid.try_to_scale(TimeScale::Ut1).is_err());
+    }
+
+    #[test]
+    fn test_tdb_minus_tt() {
+        // SOFA iauDtdb(2448939.5, 0.123, ...) = −1.2803680e-3 s, including a
+        // topocentric term of a few μs.
+        let dtdb = get_tdb_minus_tt(&JulianDay::new(2_448_939.623));
+        assert!((dtdb + 0.001_280_368).abs() < 2e-5, "{dtdb}");
+
+        let tt = Instant::new(JulianDay::new(2_448_939.623), TimeScale::Tt);
+        let tdb = tt.to_tdb();
+        assert!((tdb.jd().days_since(&tt.jd()) * SECONDS_PER_DAY - dtdb).abs() < 1e-9);
+        assert_seconds(&tdb.to_tt(), 2_448_939.623, 1e-5);
+    }
+
+    #[test]
+    fn test_leap_seconds_table() {
+        // 2016-12-31 and 2017-01-01 at 0h UTC
+        assert_eq!(get_leap_seconds(&JulianDay::new(2_457_753.5)), 36.0);
+        assert_eq!(get_leap_seconds(&JulianDay::new(2_457_754.5)), 37.0);
+        assert_eq!(get_leap_seconds(&JulianDay::new(2_441_317.5)), 10.0);
+        assert_eq!(get_leap_seconds(&JulianDay::new(2_400_000.5)), 10.0);
+    }
+
+    #[test]
+    fn test_add_duration_across_leap_second() {
+        // 2016-12-31 23:59:59 UTC, followed by the leap second 23:59:60
+        let midnight = JulianDay::new(2_457_754.5);
+        let utc = Instant::new(midnight - 1_f64 / SECONDS_PER_DAY, TimeScale::Utc);
+        let two_seconds = Duration::from_seconds(2.0);
+
+        assert_seconds(&(utc.to_tai() + two_seconds).to_utc(), 2_457_754.5, 1e-4);
+        let naive = utc + two_seconds;
+        assert_eq!(naive.scale(), TimeScale::Utc);
+        assert_seconds(&naive, 2_457_754.5 + 1_f64 / SECONDS_PER_DAY, 1e-4);
+        assert_seconds(&(naive - two_seconds), utc.jd().get_value(), 1e-4);
+    }
+}
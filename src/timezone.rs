@@ -0,0 +1,565 @@
+//! IANA time zones read from TZif files (RFC 8536), as installed with the
+//! system tzdata (`/usr/share/zoneinfo`).
+//!
+//! Offsets are looked up in the transition table of the file; instants after
+//! the last transition follow the POSIX TZ rule stored in the footer of version
+//! 2 and later files, which is all that "slim" files contain for current dates.
+
+use std::path::{Component, Path};
+
+use crate::date::{Calendar, Date};
+use crate::julian_day::JulianDay;
+use crate::local_date::UtcOffset;
+use crate::Error;
+
+/// Default directory of the system tzdata, overridden by the `TZDIR` variable.
+const ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+
+/// A time zone: the history of its UTC offsets and the rule for the future.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeZone {
+    /// Unix times of the transitions, ascending.
+    transitions: Vec<i64>,
+    /// Index in `offsets` of the local time type starting at each transition.
+    transition_types: Vec<usize>,
+    /// Local time types, the first one applies before the first transition.
+    offsets: Vec<UtcOffset>,
+    /// Rule for the instants after the last transition.
+    rule: Option<PosixRule>,
+}
+
+impl TimeZone {
+    /// A time zone with a constant offset.
+    #[must_use]
+    pub fn fixed(offset: UtcOffset) -> Self {
+        Self {
+            transitions: Vec::new(),
+            transition_types: Vec::new(),
+            offsets: vec![offset],
+            rule: None,
+        }
+    }
+
+    /// Parses the content of a TZif file, returning [`Error::InvalidTimeZone`]
+    /// if it is malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader { bytes, position: 0 };
+        let (version, counts) = reader.header()?;
+
+        if version == 1 {
+            return reader.data(&counts, 4);
+        }
+
+        // Version 2 and later repeat the data with 64-bit times after the
+        // 32-bit block, followed by the footer.
+        reader.skip(counts.data_len(4))?;
+        let (_, counts) = reader.header()?;
+        let mut zone = reader.data(&counts, 8)?;
+
+        let footer = reader.footer()?;
+        if !footer.is_empty() {
+            zone.rule = Some(PosixRule::parse(footer)?);
+        }
+
+        Ok(zone)
+    }
+
+    /// Reads a TZif file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let bytes = std::fs::read(path).map_err(|e| Error::Io(e.kind()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Reads a zone of the system tzdata by its IANA name, e.g. `Europe/Rome`,
+    /// from the directory in the `TZDIR` environment variable or `/usr/share/zoneinfo`.
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        let relative = Path::new(name);
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(Error::InvalidTimeZone(format!("invalid zone name {name}")));
+        }
+
+        let dir = std::env::var_os("TZDIR").unwrap_or_else(|| ZONEINFO_DIR.into());
+        Self::from_file(Path::new(&dir).join(relative))
+    }
+
+    /// Returns the offset from UTC in effect at a Julian Day (UTC).
+    #[must_use]
+    pub fn offset_at(&self, jd: &JulianDay) -> UtcOffset {
+        self.offset_at_unix(jd.to_unix().floor() as i64)
+    }
+
+    /// Returns the offset from UTC in effect at a local time, given as the
+    /// Julian Day of the local date and time.
+    ///
+    /// Local times skipped at the start of daylight saving time take the
+    /// offset before the change; repeated ones at its end take the later offset.
+    #[must_use]
+    pub fn offset_at_local(&self, local: &JulianDay) -> UtcOffset {
+        let local = local.to_unix().floor() as i64;
+        let guess = self.offset_at_unix(local);
+        self.offset_at_unix(local - i64::from(guess.seconds()))
+    }
+
+    fn offset_at_unix(&self, time: i64) -> UtcOffset {
+        let index = self.transitions.partition_point(|&t| t <= time);
+
+        if index == self.transitions.len() {
+            if let Some(rule) = &self.rule {
+                return rule.offset_at(time);
+            }
+        }
+
+        match index.checked_sub(1) {
+            Some(last) => self.offsets[self.transition_types[last]],
+            None => self.offsets[0],
+        }
+    }
+}
+
+/// Counts of the header of a TZif data block.
+struct Counts {
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl Counts {
+    fn data_len(&self, time_size: usize) -> usize {
+        self.timecnt * (time_size + 1)
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let slice = self
+            .position
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(self.position..end))
+            .ok_or_else(|| invalid("unexpected end of file"))?;
+        self.position += len;
+        Ok(slice)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), Error> {
+        self.take(len).map(|_| ())
+    }
+
+    fn be_u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn be_time(&mut self, size: usize) -> Result<i64, Error> {
+        let bytes = self.take(size)?;
+        Ok(bytes
+            .iter()
+            .fold(i64::from(bytes[0] as i8 >> 7), |acc, &b| {
+                (acc << 8) | i64::from(b)
+            }))
+    }
+
+    fn header(&mut self) -> Result<(u8, Counts), Error> {
+        if self.take(4)? != b"TZif" {
+            return Err(invalid("not a TZif file"));
+        }
+        let version = match self.take(1)?[0] {
+            0 => 1,
+            v @ b'2'..=b'9' => v - b'0',
+            _ => return Err(invalid("unknown TZif version")),
+        };
+        self.skip(15)?;
+
+        let mut count = || self.be_u32().map(|c| c as usize);
+        let counts = Counts {
+            isutcnt: count()?,
+            isstdcnt: count()?,
+            leapcnt: count()?,
+            timecnt: count()?,
+            typecnt: count()?,
+            charcnt: count()?,
+        };
+
+        Ok((version, counts))
+    }
+
+    fn data(&mut self, counts: &Counts, time_size: usize) -> Result<TimeZone, Error> {
+        if counts.typecnt == 0 {
+            return Err(invalid("no local time types"));
+        }
+
+        let transitions = (0..counts.timecnt)
+            .map(|_| self.be_time(time_size))
+            .collect::<Result<Vec<_>, _>>()?;
+        let transition_types = self
+            .take(counts.timecnt)?
+            .iter()
+            .map(|&i| usize::from(i))
+            .collect::<Vec<_>>();
+        if transition_types.iter().any(|&i| i >= counts.typecnt) {
+            return Err(invalid("invalid local time type index"));
+        }
+
+        let offsets = (0..counts.typecnt)
+            .map(|_| {
+                let seconds = self.be_u32()? as i32;
+                self.skip(2)?;
+                Ok(UtcOffset::from_seconds(seconds))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        self.skip(
+            counts.charcnt + counts.leapcnt * (time_size + 4) + counts.isstdcnt + counts.isutcnt,
+        )?;
+
+        Ok(TimeZone {
+            transitions,
+            transition_types,
+            offsets,
+            rule: None,
+        })
+    }
+
+    fn footer(&mut self) -> Result<&'a str, Error> {
+        let rest = &self.bytes[self.position..];
+        let footer = rest
+            .strip_prefix(b"\n")
+            .and_then(|f| f.iter().position(|&b| b == b'\n').map(|end| &f[..end]))
+            .ok_or_else(|| invalid("missing footer"))?;
+        std::str::from_utf8(footer).map_err(|_| invalid("invalid footer"))
+    }
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidTimeZone(reason.to_string())
+}
+
+/// Day of the year of a POSIX TZ rule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RuleDay {
+    /// `Jn`: day 1-365, February 29 is never counted.
+    Julian(u16),
+    /// `n`: day 0-365, February 29 is counted in leap years.
+    Ordinal(u16),
+    /// `Mm.w.d`: day `d` (0 is Sunday) of week `w` (5 is the last) of month `m`.
+    MonthWeekDay(u8, u8, u8),
+}
+
+/// The POSIX TZ rule of a TZif footer, e.g. `CET-1CEST,M3.5.0,M10.5.0/3`.
+#[derive(Debug, Clone, PartialEq)]
+struct PosixRule {
+    std_offset: i32,
+    dst: Option<DstRule>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DstRule {
+    offset: i32,
+    start: (RuleDay, i32),
+    end: (RuleDay, i32),
+}
+
+impl PosixRule {
+    fn parse(s: &str) -> Result<Self, Error> {
+        let error = || invalid(&format!("invalid TZ rule {s}"));
+        let mut rest = s;
+
+        skip_name(&mut rest).ok_or_else(error)?;
+        // POSIX offsets are positive west of Greenwich.
+        let std_offset = -parse_time(&mut rest).ok_or_else(error)?;
+
+        if rest.is_empty() {
+            return Ok(Self {
+                std_offset,
+                dst: None,
+            });
+        }
+
+        skip_name(&mut rest).ok_or_else(error)?;
+        let offset = if rest.is_empty() || rest.starts_with(',') {
+            std_offset + 3600
+        } else {
+            -parse_time(&mut rest).ok_or_else(error)?
+        };
+
+        // Without rules the US ones are the customary default.
+        let rules = rest.strip_prefix(',').unwrap_or("M3.2.0,M11.1.0");
+        let (start, end) = rules.split_once(',').ok_or_else(error)?;
+
+        Ok(Self {
+            std_offset,
+            dst: Some(DstRule {
+                offset,
+                start: parse_rule_date(start).ok_or_else(error)?,
+                end: parse_rule_date(end).ok_or_else(error)?,
+            }),
+        })
+    }
+
+    fn offset_at(&self, time: i64) -> UtcOffset {
+        let Some(dst) = &self.dst else {
+            return UtcOffset::from_seconds(self.std_offset);
+        };
+
+        let local = Date::from_unix((time + i64::from(self.std_offset)) as f64);
+        let year = local.year;
+        let start = day_start(year, dst.start.0) + i64::from(dst.start.1 - self.std_offset);
+        let end = day_start(year, dst.end.0) + i64::from(dst.end.1 - dst.offset);
+
+        let is_dst = if start < end {
+            start <= time && time < end
+        } else {
+            // Southern hemisphere, daylight saving time spans the new year.
+            !(end <= time && time < start)
+        };
+
+        UtcOffset::from_seconds(if is_dst { dst.offset } else { self.std_offset })
+    }
+}
+
+/// Unix time of 0h UTC of a date of the proleptic Gregorian calendar.
+fn unix_day(year: i16, month: u8, day: u8) -> i64 {
+    let date = Date::new(year, month, f64::from(day)).with_calendar(Calendar::ProlepticGregorian);
+    JulianDay::from_date(&date).to_unix() as i64
+}
+
+fn days_in_month(year: i16, month: u8) -> i64 {
+    i64::from(Calendar::ProlepticGregorian.days_in_month(year, month))
+}
+
+/// Unix time of the start of the day of a rule in a year, as if the local time were UTC.
+fn day_start(year: i16, day: RuleDay) -> i64 {
+    let days = match day {
+        RuleDay::Julian(n) => {
+            let n = i64::from(n) - 1;
+            if n >= 59 && days_in_month(year, 2) == 29 {
+                n + 1
+            } else {
+                n
+            }
+        }
+        RuleDay::Ordinal(n) => i64::from(n),
+        RuleDay::MonthWeekDay(month, week, weekday) => {
+            let first = unix_day(year, month, 1);
+            // 1970-01-01 was a Thursday.
+            let first_weekday = (first / 86400 + 4).rem_euclid(7);
+            let mut day =
+                (i64::from(weekday) - first_weekday).rem_euclid(7) + (i64::from(week) - 1) * 7;
+            while day >= days_in_month(year, month) {
+                day -= 7;
+            }
+            return first + day * 86400;
+        }
+    };
+
+    unix_day(year, 1, 1) + days * 86400
+}
+
+/// Skips a zone abbreviation, either alphabetic or quoted in angle brackets.
+fn skip_name(s: &mut &str) -> Option<()> {
+    let len = if let Some(quoted) = s.strip_prefix('<') {
+        quoted.find('>')? + 2
+    } else {
+        s.find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(s.len())
+    };
+    if len < 3 {
+        return None;
+    }
+    *s = &s[len..];
+    Some(())
+}
+
+/// Parses a signed `hh[:mm[:ss]]` time in seconds.
+fn parse_time(s: &mut &str) -> Option<i32> {
+    let end = s
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, ':' | '+' | '-')))
+        .unwrap_or(s.len());
+    let (time, rest) = s.split_at(end);
+    *s = rest;
+
+    let (sign, time) = match time.as_bytes().first()? {
+        b'-' => (-1, &time[1..]),
+        b'+' => (1, &time[1..]),
+        _ => (1, time),
+    };
+
+    let mut seconds = 0;
+    let mut fields = 0;
+    for (field, scale) in time.split(':').zip([3600, 60, 1]) {
+        seconds += field.parse::<i32>().ok()? * scale;
+        fields += 1;
+    }
+    if fields == 0 || time.split(':').count() > 3 {
+        return None;
+    }
+
+    Some(sign * seconds)
+}
+
+/// Parses a rule date with its optional time, `date[/time]`.
+fn parse_rule_date(s: &str) -> Option<(RuleDay, i32)> {
+    let (date, time) = match s.split_once('/') {
+        Some((date, mut time)) => (date, parse_time(&mut time)?),
+        None => (s, 7200),
+    };
+
+    let day = if let Some(n) = date.strip_prefix('J') {
+        RuleDay::Julian(n.parse().ok().filter(|n| (1..=365).contains(n))?)
+    } else if let Some(mwd) = date.strip_prefix('M') {
+        let mut fields = mwd.split('.').map(str::parse::<u8>);
+        let month = fields.next()?.ok().filter(|m| (1..=12).contains(m))?;
+        let week = fields.next()?.ok().filter(|w| (1..=5).contains(w))?;
+        let weekday = fields.next()?.ok().filter(|d| *d <= 6)?;
+        RuleDay::MonthWeekDay(month, week, weekday)
+    } else {
+        RuleDay::Ordinal(date.parse().ok().filter(|n| *n <= 365)?)
+    };
+
+    Some((day, time))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Builds a version 2 TZif file.
+    fn tzif(transitions: &[(i64, u8)], offsets: &[i32], footer: &str) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let header = |bytes: &mut Vec<u8>, timecnt: usize, typecnt: usize| {
+            bytes.extend_from_slice(b"TZif2");
+            bytes.extend_from_slice(&[0; 15]);
+            for count in [0, 0, 0, timecnt, typecnt, 4] {
+                bytes.extend_from_slice(&(count as u32).to_be_bytes());
+            }
+        };
+
+        // Version 1 block with a single local time type
+        header(&mut bytes, 0, 1);
+        bytes.extend_from_slice(&offsets[0].to_be_bytes());
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(b"UTC\0");
+
+        header(&mut bytes, transitions.len(), offsets.len());
+        for (time, _) in transitions {
+            bytes.extend_from_slice(&time.to_be_bytes());
+        }
+        bytes.extend(transitions.iter().map(|(_, index)| index));
+        for offset in offsets {
+            bytes.extend_from_slice(&offset.to_be_bytes());
+            bytes.extend_from_slice(&[0, 0]);
+        }
+        bytes.extend_from_slice(b"UTC\0");
+        bytes.extend_from_slice(format!("\n{footer}\n").as_bytes());
+        bytes
+    }
+
+    fn offset_at(zone: &TimeZone, unix: f64) -> i32 {
+        zone.offset_at(&JulianDay::from_unix(unix)).seconds()
+    }
+
+    const ROME_RULE: &str = "CET-1CEST,M3.5.0,M10.5.0/3";
+
+    #[test]
+    fn test_transitions_then_footer_rule() {
+        // 2024-03-31T01:00Z to CEST, 2024-10-27T01:00Z back to CET
+        let bytes = tzif(
+            &[(1_711_846_800, 1), (1_729_990_800, 0)],
+            &[3600, 7200],
+            ROME_RULE,
+        );
+        let zone = TimeZone::from_bytes(&bytes).unwrap();
+
+        assert_eq!(offset_at(&zone, 1_704_067_200.0), 3600); // 2024-01-01
+        assert_eq!(offset_at(&zone, 1_711_846_799.0), 3600);
+        assert_eq!(offset_at(&zone, 1_711_846_800.0), 7200);
+        assert_eq!(offset_at(&zone, 1_719_792_000.0), 7200); // 2024-07-01
+        assert_eq!(offset_at(&zone, 1_729_990_800.0), 3600);
+        assert_eq!(offset_at(&zone, 1_751_328_000.0), 7200); // 2025-07-01
+        assert_eq!(offset_at(&zone, 1_764_547_200.0), 3600); // 2025-12-01
+    }
+
+    #[test]
+    fn test_slim_file_rule_boundaries() {
+        let zone = TimeZone::from_bytes(&tzif(&[], &[3600], ROME_RULE)).unwrap();
+
+        // 2025-03-30T01:00Z and 2025-10-26T01:00Z
+        assert_eq!(offset_at(&zone, 1_743_296_399.0), 3600);
+        assert_eq!(offset_at(&zone, 1_743_296_400.0), 7200);
+        assert_eq!(offset_at(&zone, 1_761_440_399.0), 7200);
+        assert_eq!(offset_at(&zone, 1_761_440_400.0), 3600);
+    }
+
+    #[test]
+    fn test_southern_hemisphere_rule() {
+        let zone =
+            TimeZone::from_bytes(&tzif(&[], &[36_000], "AEST-10AEDT,M10.1.0,M4.1.0/3")).unwrap();
+
+        assert_eq!(offset_at(&zone, 1_735_689_600.0), 39_600); // 2025-01-01
+        assert_eq!(offset_at(&zone, 1_751_328_000.0), 36_000); // 2025-07-01
+        assert_eq!(offset_at(&zone, 1_767_225_600.0), 39_600); // 2026-01-01
+    }
+
+    #[test]
+    fn test_rules_without_dst() {
+        let zone = TimeZone::from_bytes(&tzif(&[], &[19_800], "IST-5:30")).unwrap();
+        assert_eq!(offset_at(&zone, 1_751_328_000.0), 19_800);
+
+        let zone = TimeZone::from_bytes(&tzif(&[], &[-10_800], "<-03>3")).unwrap();
+        assert_eq!(offset_at(&zone, 1_751_328_000.0), -10_800);
+
+        let zone = TimeZone::fixed(UtcOffset::from_hours_minutes(9, 0));
+        assert_eq!(offset_at(&zone, 0.0), 32_400);
+    }
+
+    #[test]
+    fn test_offset_at_local() {
+        let zone = TimeZone::from_bytes(&tzif(&[], &[3600], ROME_RULE)).unwrap();
+        let local = Date::from_full_date(2025, 7, 1.0, 12, 0, 0.0);
+        assert_eq!(zone.offset_at_local(&local.to_julian_day()).seconds(), 7200);
+
+        // 02:30 on 2025-03-30 does not exist, the offset before the change applies.
+        let skipped = Date::from_full_date(2025, 3, 30.0, 2, 30, 0.0);
+        assert_eq!(
+            zone.offset_at_local(&skipped.to_julian_day()).seconds(),
+            3600
+        );
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        assert!(matches!(
+            TimeZone::from_bytes(b"not a zone"),
+            Err(Error::InvalidTimeZone(_))
+        ));
+
+        let bytes = tzif(&[(0, 1)], &[3600, 7200], ROME_RULE);
+        assert!(TimeZone::from_bytes(&bytes[..bytes.len() - 30]).is_err());
+
+        let bytes = tzif(&[(0, 2)], &[3600, 7200], ROME_RULE);
+        assert!(TimeZone::from_bytes(&bytes).is_err());
+
+        let bytes = tzif(&[], &[3600], "CET-1CEST,M13.5.0,M10.5.0");
+        assert!(TimeZone::from_bytes(&bytes).is_err());
+
+        assert!(matches!(
+            TimeZone::from_name("../etc/passwd"),
+            Err(Error::InvalidTimeZone(_))
+        ));
+    }
+}
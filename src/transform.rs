@@ -14,8 +14,8 @@ pub fn ra_to_deg(ra: &RightAscension) -> f64 {
 #[must_use]
 pub fn deg_to_ra(deg: f64) -> RightAscension {
     let hours = (deg / 15_f64) as u8;
-    let minutes = ((deg - f64::from(hours * 15)) * 4_f64) as u8;
-    let secs = (deg - f64::from(hours * 15) - (f64::from(minutes) / 4_f64)) * 240_f64;
+    let minutes = ((deg - f64::from(hours) * 15_f64) * 4_f64) as u8;
+    let secs = (deg - f64::from(hours) * 15_f64 - (f64::from(minutes) / 4_f64)) * 240_f64;
 
     RightAscension::new(hours, minutes, secs)
 }
@@ -23,7 +23,7 @@ pub fn deg_to_ra(deg: f64) -> RightAscension {
 #[must_use]
 pub fn dec_to_deg(dec: &DegMinSec) -> f64 {
     let mut degrees =
-        f64::from(dec.degrees) + f64::from(dec.minutes) / 60_f64 + dec.seconds as f64 / 3600_f64;
+        f64::from(dec.degrees) + f64::from(dec.minutes) / 60_f64 + dec.seconds / 3600_f64;
 
     if dec.negative {
         degrees *= -1.0;
@@ -35,21 +35,17 @@ pub fn dec_to_deg(dec: &DegMinSec) -> f64 {
 /// Utility to go easily from a decimal degree to a Degree-minutes
 #[must_use]
 pub fn deg_to_dms(degrees: f64) -> DegMinSec {
-    let mut n_deg: i16 = degrees as i16;
-    let mut n_minutes: f64 = 60_f64 * (degrees.abs() - f64::from(n_deg.abs()));
-    let mut n_secs: f64 = 60.0 * (n_minutes - f64::from(n_minutes as u8));
-
-    if n_secs > 59.0 {
-        n_secs = 0.0;
-        n_minutes += 1.0;
-    }
-
-    if n_minutes > 59.0 {
-        n_minutes = 0.0;
-        n_deg += 1;
+    let total_secs = degrees.abs() * 3600_f64;
+    let n_deg = (total_secs / 3600_f64).trunc();
+    let n_minutes = ((total_secs - n_deg * 3600_f64) / 60_f64).trunc();
+    let n_secs = total_secs - n_deg * 3600_f64 - n_minutes * 60_f64;
+
+    DegMinSec {
+        negative: degrees < 0.0,
+        degrees: n_deg as i16,
+        minutes: n_minutes as u8,
+        seconds: n_secs,
     }
-
-    DegMinSec::new(n_deg, n_minutes as u8, n_secs)
 }
 
 #[cfg(test)]
@@ -98,4 +94,21 @@ mod tests {
         let test_dms = DegMinSec::new(-59, 11, 36.96);
         assert_eq!(deg_to_dms(-59.1936), test_dms);
     }
+
+    #[test]
+    fn test_deg_to_dms_minutes_above_59() {
+        assert_eq!(deg_to_dms(10.99), DegMinSec::new(10, 59, 24.0));
+    }
+
+    #[test]
+    fn test_deg_to_dms_negative_below_one_degree() {
+        let dms = deg_to_dms(-0.5);
+        assert!(dms.negative);
+        assert_eq!((dms.degrees, dms.minutes), (0, 30));
+    }
+
+    #[test]
+    fn test_deg_to_ra_above_255_deg() {
+        assert_eq!(deg_to_ra(350.0), RightAscension::new(23, 20, 0.0));
+    }
 }